@@ -0,0 +1,73 @@
+//! Minimal `--flag value` parsing shared by the subcommands.
+
+use crate::error::{self, Error};
+use crate::reader::{Encoding, FramesReader};
+use capnp::message::ReaderOptions;
+use std::fmt::Display;
+use std::fs;
+use std::slice;
+use std::str::FromStr;
+
+/// Parses the value following `flag`, e.g. the `4` in `--width 4`.
+pub fn value<T>(flag: &str, value: Option<&String>) -> Result<T, Error>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let value = value.ok_or_else(|| Error::Usage(format!("{} expects a value", flag)))?;
+    value
+        .parse()
+        .map_err(|e| Error::Usage(format!("invalid value {:?} for {}: {}", value, flag, e)))
+}
+
+pub fn unknown_option(flag: &str) -> Error {
+    Error::Usage(format!("unknown option {}", flag))
+}
+
+/// Options shared by every subcommand that reads a frames file.
+pub struct InputOptions {
+    pub path: String,
+    pub encoding: Option<Encoding>,
+    pub reader_options: ReaderOptions,
+}
+
+impl Default for InputOptions {
+    fn default() -> Self {
+        InputOptions {
+            path: "data.bin".to_string(),
+            encoding: None,
+            reader_options: ReaderOptions::new(),
+        }
+    }
+}
+
+impl InputOptions {
+    /// Takes `arg` (and its value from `args`) if it is an input option or
+    /// the input path, and returns whether it did.
+    pub fn parse_arg(&mut self, arg: &str, args: &mut slice::Iter<String>) -> Result<bool, Error> {
+        match arg {
+            "--packed" => self.encoding = Some(Encoding::Packed),
+            "--traversal-limit" => {
+                self.reader_options
+                    .traversal_limit_in_words(value(arg, args.next())?);
+            }
+            "--nesting-limit" => {
+                self.reader_options.nesting_limit(value(arg, args.next())?);
+            }
+            flag if flag.starts_with("--") => return Ok(false),
+            path => self.path = path.to_string(),
+        }
+        Ok(true)
+    }
+
+    pub fn open(&self) -> Result<FramesReader<fs::File>, Error> {
+        self.open_path(&self.path)
+    }
+
+    /// Opens `path` rather than `self.path`, for subcommands that read
+    /// several files with the same options.
+    pub fn open_path(&self, path: &str) -> Result<FramesReader<fs::File>, Error> {
+        let f = error::open(path)?;
+        Ok(FramesReader::new(f, self.reader_options, self.encoding))
+    }
+}
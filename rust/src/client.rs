@@ -0,0 +1,285 @@
+use crate::calculator_capnp::calculator;
+use crate::error::Error as CliError;
+use crate::rpc;
+use capnp::capability::Promise;
+use capnp::Error;
+use capnp_rpc::pry;
+use tokio::runtime::current_thread;
+
+type ScenarioResult = Result<(), Box<dyn std::error::Error>>;
+type Scenario = fn(&mut current_thread::Runtime, &calculator::Client) -> ScenarioResult;
+
+/// The scenarios from `ts/src/client.ts`, in the same order.
+const SCENARIOS: &[(&str, Scenario)] = &[
+    ("Evaluating a literal", evaluate_literal),
+    ("Using add and subtract", add_and_subtract),
+    ("Pipeline eval() calls", pipeline_eval_calls),
+    ("Defining functions", define_functions),
+    ("Using a callback", use_callback),
+];
+
+fn check_eq(actual: f64, expected: f64) -> ScenarioResult {
+    if (actual - expected).abs() > f64::EPSILON {
+        return Err(format!("expected {}, got {}", expected, actual).into());
+    }
+    Ok(())
+}
+
+fn get_operator(
+    calculator: &calculator::Client,
+    op: calculator::Operator,
+) -> calculator::function::Client {
+    let mut request = calculator.get_operator_request();
+    request.get().set_op(op);
+    request.send().pipeline.get_func()
+}
+
+fn read_value(
+    runtime: &mut current_thread::Runtime,
+    value: &calculator::value::Client,
+) -> Result<f64, Box<dyn std::error::Error>> {
+    let response = runtime.block_on(value.read_request().send().promise)?;
+    Ok(response.get()?.get_value())
+}
+
+/// Evaluates the literal 123. `evaluate()` returns a `Value` living on the
+/// server, but thanks to pipelining, reading it back still only takes one
+/// network round trip.
+fn evaluate_literal(
+    runtime: &mut current_thread::Runtime,
+    calculator: &calculator::Client,
+) -> ScenarioResult {
+    let mut request = calculator.evaluate_request();
+    request.get().init_expression().set_literal(123.0);
+    let value = request.send().pipeline.get_value();
+    check_eq(read_value(runtime, &value)?, 123.0)
+}
+
+/// Evaluates 123 + 45 - 67 using operators obtained from `getOperator()`.
+fn add_and_subtract(
+    runtime: &mut current_thread::Runtime,
+    calculator: &calculator::Client,
+) -> ScenarioResult {
+    let add = get_operator(calculator, calculator::Operator::Add);
+    let subtract = get_operator(calculator, calculator::Operator::Subtract);
+
+    let mut request = calculator.evaluate_request();
+    {
+        let mut subtract_call = request.get().init_expression().init_call();
+        subtract_call.set_function(subtract);
+        let mut subtract_params = subtract_call.init_params(2);
+        subtract_params.reborrow().get(1).set_literal(67.0);
+
+        let mut add_call = subtract_params.get(0).init_call();
+        add_call.set_function(add);
+        let mut add_params = add_call.init_params(2);
+        add_params.reborrow().get(0).set_literal(123.0);
+        add_params.get(1).set_literal(45.0);
+    }
+    let value = request.send().pipeline.get_value();
+    check_eq(read_value(runtime, &value)?, 101.0)
+}
+
+/// Evaluates 4 * 6, then passes the resulting `Value` back to the server in
+/// two more requests that add 3 and 5, before the first one has returned.
+fn pipeline_eval_calls(
+    runtime: &mut current_thread::Runtime,
+    calculator: &calculator::Client,
+) -> ScenarioResult {
+    let add = get_operator(calculator, calculator::Operator::Add);
+    let multiply = get_operator(calculator, calculator::Operator::Multiply);
+
+    let mult_result = {
+        let mut request = calculator.evaluate_request();
+        {
+            let mut multiply_call = request.get().init_expression().init_call();
+            multiply_call.set_function(multiply);
+            let mut multiply_params = multiply_call.init_params(2);
+            multiply_params.reborrow().get(0).set_literal(4.0);
+            multiply_params.get(1).set_literal(6.0);
+        }
+        request.send().pipeline.get_value()
+    };
+
+    let add_to_result = |literal: f64| {
+        let mut request = calculator.evaluate_request();
+        {
+            let mut add_call = request.get().init_expression().init_call();
+            add_call.set_function(add.clone());
+            let mut add_params = add_call.init_params(2);
+            add_params
+                .reborrow()
+                .get(0)
+                .set_previous_result(mult_result.clone());
+            add_params.get(1).set_literal(literal);
+        }
+        request.send().pipeline.get_value()
+    };
+    let add3_result = add_to_result(3.0);
+    let add5_result = add_to_result(5.0);
+
+    check_eq(read_value(runtime, &add3_result)?, 27.0)?;
+    check_eq(read_value(runtime, &add5_result)?, 29.0)
+}
+
+/// Defines two functions on the server and calls them:
+///
+///   f(x, y) = x * 100 + y
+///   g(x) = f(x, x + 1) * 2
+///   f(12, 34)
+///   g(21)
+fn define_functions(
+    runtime: &mut current_thread::Runtime,
+    calculator: &calculator::Client,
+) -> ScenarioResult {
+    let add = get_operator(calculator, calculator::Operator::Add);
+    let multiply = get_operator(calculator, calculator::Operator::Multiply);
+
+    let f = {
+        let mut request = calculator.def_function_request();
+        request.get().set_param_count(2);
+        {
+            let mut add_call = request.get().init_body().init_call();
+            add_call.set_function(add.clone());
+            let mut add_params = add_call.init_params(2);
+            add_params.reborrow().get(1).set_parameter(1);
+
+            let mut multiply_call = add_params.get(0).init_call();
+            multiply_call.set_function(multiply.clone());
+            let mut multiply_params = multiply_call.init_params(2);
+            multiply_params.reborrow().get(0).set_parameter(0);
+            multiply_params.get(1).set_literal(100.0);
+        }
+        request.send().pipeline.get_func()
+    };
+
+    let g = {
+        let mut request = calculator.def_function_request();
+        request.get().set_param_count(1);
+        {
+            let mut multiply_call = request.get().init_body().init_call();
+            multiply_call.set_function(multiply);
+            let mut multiply_params = multiply_call.init_params(2);
+            multiply_params.reborrow().get(1).set_literal(2.0);
+
+            let mut f_call = multiply_params.get(0).init_call();
+            f_call.set_function(f.clone());
+            let mut f_params = f_call.init_params(2);
+            f_params.reborrow().get(0).set_parameter(0);
+
+            let mut add_call = f_params.get(1).init_call();
+            add_call.set_function(add);
+            let mut add_params = add_call.init_params(2);
+            add_params.reborrow().get(0).set_parameter(0);
+            add_params.get(1).set_literal(1.0);
+        }
+        request.send().pipeline.get_func()
+    };
+
+    let f_eval = {
+        let mut request = calculator.evaluate_request();
+        {
+            let mut f_call = request.get().init_expression().init_call();
+            f_call.set_function(f);
+            let mut f_params = f_call.init_params(2);
+            f_params.reborrow().get(0).set_literal(12.0);
+            f_params.get(1).set_literal(34.0);
+        }
+        request.send().pipeline.get_value()
+    };
+
+    let g_eval = {
+        let mut request = calculator.evaluate_request();
+        {
+            let mut g_call = request.get().init_expression().init_call();
+            g_call.set_function(g);
+            g_call.init_params(1).get(0).set_literal(21.0);
+        }
+        request.send().pipeline.get_value()
+    };
+
+    check_eq(read_value(runtime, &f_eval)?, 1234.0)?;
+    check_eq(read_value(runtime, &g_eval)?, 4244.0)
+}
+
+/// A `Function` hosted on the client: the server calls back into it.
+struct PowerFunction;
+
+impl calculator::function::Server for PowerFunction {
+    fn call(
+        &mut self,
+        params: calculator::function::CallParams,
+        mut results: calculator::function::CallResults,
+    ) -> Promise<(), Error> {
+        let params = pry!(pry!(params.get()).get_params());
+        if params.len() != 2 {
+            return Promise::err(Error::failed("Wrong number of parameters".to_string()));
+        }
+        results.get().set_value(params.get(0).powf(params.get(1)));
+        Promise::ok(())
+    }
+}
+
+/// Computes 2^(4 + 5) with an exponent function the server does not have,
+/// which takes two round trips since the server has to call back to us.
+fn use_callback(
+    runtime: &mut current_thread::Runtime,
+    calculator: &calculator::Client,
+) -> ScenarioResult {
+    let add = get_operator(calculator, calculator::Operator::Add);
+
+    let mut request = calculator.evaluate_request();
+    {
+        let mut pow_call = request.get().init_expression().init_call();
+        pow_call.set_function(
+            calculator::function::ToClient::new(PowerFunction).into_client::<capnp_rpc::Server>(),
+        );
+        let mut pow_params = pow_call.init_params(2);
+        pow_params.reborrow().get(0).set_literal(2.0);
+
+        let mut add_call = pow_params.get(1).init_call();
+        add_call.set_function(add);
+        let mut add_params = add_call.init_params(2);
+        add_params.reborrow().get(0).set_literal(4.0);
+        add_params.get(1).set_literal(5.0);
+    }
+    let value = request.send().pipeline.get_value();
+    check_eq(read_value(runtime, &value)?, 512.0)
+}
+
+/// Runs every scenario, printing PASS or FAIL for each, and returns how many
+/// failed.
+fn run_scenarios(runtime: &mut current_thread::Runtime, calculator: &calculator::Client) -> usize {
+    let mut failures = 0;
+    for (name, scenario) in SCENARIOS {
+        println!("{}...", name);
+        match scenario(runtime, calculator) {
+            Ok(()) => println!("PASS"),
+            Err(e) => {
+                println!("FAIL: {}", e);
+                failures += 1;
+            }
+        }
+    }
+    failures
+}
+
+pub fn client_main() -> Result<(), CliError> {
+    // Set up async runtime
+    let mut runtime = current_thread::Runtime::new()?;
+
+    // Establish TCP connection to server
+    let addr: std::net::SocketAddr = ([127, 0, 0, 1], 9494).into();
+    println!("Connecting to server on {}", addr);
+    let calculator: calculator::Client = rpc::connect(&mut runtime, &addr)?;
+
+    let failures = run_scenarios(&mut runtime, &calculator);
+    if failures > 0 {
+        return Err(CliError::ScenariosFailed {
+            failed: failures,
+            total: SCENARIOS.len(),
+        });
+    }
+    println!("All done!");
+    Ok(())
+}
@@ -0,0 +1,159 @@
+//! Splitting a frames file into one `Frames` message per stream.
+
+use crate::args::{self, InputOptions};
+use crate::error::Error;
+use crate::frames_capnp::{frame, frames, rational};
+use crate::rational::Rational;
+use crate::reader::{Encoding, FramesMessage};
+use capnp::message::{Builder, HeapAllocator};
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::fs;
+use std::path::PathBuf;
+
+/// Where a frame of some stream sits in the input.
+struct Entry {
+    /// Position of its message in the slice given to `demux`.
+    message: usize,
+    position: u32,
+    pts: Rational,
+}
+
+pub struct Demuxed {
+    /// One message per stream id, holding that stream's frames sorted by pts.
+    pub streams: BTreeMap<u32, Builder<HeapAllocator>>,
+    /// Frames of a kind this build does not know, which have no stream id.
+    pub skipped: usize,
+}
+
+/// The stream id and pts of `frame`, or `None` for a kind this build does
+/// not know, which has no stream id.
+pub fn stream_and_pts(
+    frame: frame::Reader<'_>,
+) -> capnp::Result<Option<(u32, rational::Reader<'_>)>> {
+    match frame.which() {
+        Ok(frame::AudioFrame(af)) => {
+            let af = af?;
+            Ok(Some((af.get_stream_id(), af.get_pts()?)))
+        }
+        Ok(frame::VideoFrame(vf)) => {
+            let vf = vf?;
+            Ok(Some((vf.get_stream_id(), vf.get_pts()?)))
+        }
+        Err(capnp::NotInSchema(_)) => Ok(None),
+    }
+}
+
+fn index_message(
+    slot: usize,
+    message: &FramesMessage,
+    next_index: &mut usize,
+    streams: &mut BTreeMap<u32, Vec<Entry>>,
+    skipped: &mut usize,
+) -> Result<(), Error> {
+    let frames = message
+        .get()
+        .and_then(|root| root.get_frames())
+        .map_err(|e| message.explain(e))?;
+    for (position, frame) in frames.iter().enumerate() {
+        let index = *next_index;
+        *next_index += 1;
+
+        let (stream, pts) = match stream_and_pts(frame).map_err(|e| message.explain(e))? {
+            Some(key) => key,
+            None => {
+                *skipped += 1;
+                continue;
+            }
+        };
+        let pts = Rational::try_from(pts).map_err(|e| Error::BadFrame {
+            index,
+            reason: format!("invalid pts: {}", e),
+        })?;
+        streams.entry(stream).or_default().push(Entry {
+            message: slot,
+            position: position as u32,
+            pts,
+        });
+    }
+    Ok(())
+}
+
+pub fn copy_frame(mut out: frame::Builder, frame: frame::Reader) -> capnp::Result<()> {
+    match frame.which()? {
+        frame::AudioFrame(af) => out.set_audio_frame(af?),
+        frame::VideoFrame(vf) => out.set_video_frame(vf?),
+    }
+}
+
+/// Splits `messages` by stream id. Frames are copied as they are; only
+/// their order changes, by pts and then by position in the input.
+pub fn demux(messages: &[FramesMessage]) -> Result<Demuxed, Error> {
+    let mut streams = BTreeMap::new();
+    let (mut next_index, mut skipped) = (0, 0);
+    for (slot, message) in messages.iter().enumerate() {
+        index_message(slot, message, &mut next_index, &mut streams, &mut skipped)?;
+    }
+
+    let mut demuxed = Demuxed {
+        streams: BTreeMap::new(),
+        skipped,
+    };
+    for (stream, mut entries) in streams {
+        entries.sort_by_key(|entry| entry.pts);
+        let mut builder = Builder::new_default();
+        {
+            let mut list = builder
+                .init_root::<frames::Builder>()
+                .init_frames(entries.len() as u32);
+            for (i, entry) in entries.iter().enumerate() {
+                let frames = messages[entry.message].get()?.get_frames()?;
+                copy_frame(list.reborrow().get(i as u32), frames.get(entry.position))?;
+            }
+        }
+        demuxed.streams.insert(stream, builder);
+    }
+    Ok(demuxed)
+}
+
+pub fn demux_main(args: &[String]) -> Result<(), Error> {
+    let mut input = InputOptions::default();
+    let mut output_dir = PathBuf::from(".");
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--output-dir" => output_dir = args::value::<String>(arg, args.next())?.into(),
+            _ if input.parse_arg(arg, &mut args)? => {}
+            flag => return Err(args::unknown_option(flag)),
+        }
+    }
+
+    let mut reader = input.open()?;
+    let messages = reader.by_ref().collect::<Result<Vec<_>, _>>()?;
+    let demuxed = demux(&messages)?;
+    if demuxed.streams.is_empty() {
+        return Err(Error::NoFrames(input.path));
+    }
+
+    // Outputs keep the encoding of the input.
+    let encoding = reader.encoding().unwrap_or(Encoding::Unpacked);
+    fs::create_dir_all(&output_dir)?;
+    for (stream, message) in &demuxed.streams {
+        let path = output_dir.join(format!("stream-{}.bin", stream));
+        let mut f = fs::File::create(&path)?;
+        encoding.write_message(&mut f, message)?;
+        let frames = message
+            .get_root_as_reader::<frames::Reader>()?
+            .get_frames()?;
+        println!(
+            "stream {}: {} frames to {}",
+            stream,
+            frames.len(),
+            path.display()
+        );
+    }
+    if demuxed.skipped > 0 {
+        println!("skipped {} frames of unknown kinds", demuxed.skipped);
+    }
+    Ok(())
+}
@@ -0,0 +1,373 @@
+use crate::args::{self, InputOptions};
+use crate::error::Error;
+use crate::frames::pixel_format_name;
+use crate::frames_capnp::{frame, rational};
+use crate::rational::Rational;
+use crate::reader::FramesMessage;
+use std::collections::{HashMap, VecDeque};
+use std::convert::TryFrom;
+
+/// The parts of a frame that `diff` compares, as text.
+struct FrameView<'a> {
+    kind: String,
+    fields: Vec<(&'static str, String)>,
+    data: &'a [u8],
+}
+
+impl FrameView<'_> {
+    fn summary(&self) -> String {
+        let mut summary = self.kind.clone();
+        for (name, value) in &self.fields {
+            if *name == "streamId" || *name == "pts" {
+                summary += &format!(", {} {}", name, value);
+            }
+        }
+        summary
+    }
+
+    /// What makes two frames the same frame: their kind, stream and pts.
+    fn identity(&self) -> (&str, Vec<&str>) {
+        let key = self
+            .fields
+            .iter()
+            .filter(|(name, _)| *name == "streamId" || *name == "pts")
+            .map(|(_, value)| value.as_str());
+        (&self.kind, key.collect())
+    }
+}
+
+/// One step of walking two frame sequences side by side.
+#[derive(Debug, PartialEq)]
+enum Step {
+    /// Old frame `.0` is new frame `.1`.
+    Matched(usize, usize),
+    Removed(usize),
+    Added(usize),
+}
+
+/// Pairs up frames with the same identity, the nth occurrence in `old`
+/// with the nth in `new`, and lists the steps in the order of `new`, with
+/// removed frames where they used to be.
+fn align<K: std::hash::Hash + Eq>(old: &[K], new: &[K]) -> Vec<Step> {
+    let mut unmatched: HashMap<&K, VecDeque<usize>> = HashMap::new();
+    for (i, key) in old.iter().enumerate() {
+        unmatched.entry(key).or_default().push_back(i);
+    }
+    let pairs: Vec<Option<usize>> = new
+        .iter()
+        .map(|key| unmatched.get_mut(key).and_then(VecDeque::pop_front))
+        .collect();
+
+    let mut matched = vec![false; old.len()];
+    for i in pairs.iter().flatten() {
+        matched[*i] = true;
+    }
+    let mut steps = Vec::new();
+    let mut removed = (0..old.len()).filter(|i| !matched[*i]).peekable();
+    for (j, pair) in pairs.into_iter().enumerate() {
+        match pair {
+            Some(i) => {
+                while let Some(r) = removed.next_if(|r| *r < i) {
+                    steps.push(Step::Removed(r));
+                }
+                steps.push(Step::Matched(i, j));
+            }
+            None => steps.push(Step::Added(j)),
+        }
+    }
+    steps.extend(removed.map(Step::Removed));
+    steps
+}
+
+/// Within each run of unmatched steps, pairs every added frame with the
+/// first removed frame of the run that `pairs_with` it, so that a frame
+/// whose stream or pts changed is compared rather than removed and added.
+fn pair_changed(steps: Vec<Step>, pairs_with: impl Fn(usize, usize) -> bool) -> Vec<Step> {
+    let mut paired = Vec::with_capacity(steps.len());
+    let mut steps = steps.into_iter().peekable();
+    while let Some(step) = steps.next() {
+        if let Step::Matched(..) = step {
+            paired.push(step);
+            continue;
+        }
+        let mut run = vec![step];
+        while let Some(step) = steps.next_if(|step| !matches!(step, Step::Matched(..))) {
+            run.push(step);
+        }
+
+        let mut removed: Vec<usize> = run
+            .iter()
+            .filter_map(|step| match step {
+                Step::Removed(i) => Some(*i),
+                _ => None,
+            })
+            .collect();
+        let mut partners = HashMap::new();
+        for step in &run {
+            if let Step::Added(j) = *step {
+                if let Some(k) = removed.iter().position(|i| pairs_with(*i, j)) {
+                    partners.insert(j, removed.remove(k));
+                }
+            }
+        }
+        for step in run {
+            match step {
+                Step::Added(j) => paired.push(match partners.get(&j) {
+                    Some(i) => Step::Matched(*i, j),
+                    None => Step::Added(j),
+                }),
+                Step::Removed(i) if !removed.contains(&i) => {}
+                step => paired.push(step),
+            }
+        }
+    }
+    paired
+}
+
+/// Equal pts compare equal whatever their time base; invalid ones are
+/// compared as they are.
+fn pts_field(pts: rational::Reader) -> String {
+    match Rational::try_from(pts) {
+        Ok(pts) => pts.to_string(),
+        Err(_) => format!("{}/{}", pts.get_num(), pts.get_den()),
+    }
+}
+
+fn view(frame: frame::Reader<'_>) -> capnp::Result<FrameView<'_>> {
+    Ok(match frame.which() {
+        Ok(frame::AudioFrame(af)) => {
+            let af = af?;
+            FrameView {
+                kind: "audio".to_string(),
+                fields: vec![
+                    ("streamId", af.get_stream_id().to_string()),
+                    ("pts", pts_field(af.get_pts()?)),
+                    ("channels", af.get_channels().to_string()),
+                    ("numSamples", af.get_num_samples().to_string()),
+                ],
+                data: af.get_data()?,
+            }
+        }
+        Ok(frame::VideoFrame(vf)) => {
+            let vf = vf?;
+            FrameView {
+                kind: "video".to_string(),
+                fields: vec![
+                    ("streamId", vf.get_stream_id().to_string()),
+                    ("pts", pts_field(vf.get_pts()?)),
+                    ("width", vf.get_width().to_string()),
+                    ("height", vf.get_height().to_string()),
+                    ("pixelFormat", pixel_format_name(vf.get_pixel_format())),
+                ],
+                data: vf.get_data()?,
+            }
+        }
+        Err(capnp::NotInSchema(n)) => FrameView {
+            kind: format!("unknown variant {}", n),
+            fields: Vec::new(),
+            data: &[],
+        },
+    })
+}
+
+fn read_all(input: &InputOptions, path: &str) -> Result<Vec<FramesMessage>, Error> {
+    let messages = input.open_path(path)?.collect::<Result<Vec<_>, _>>()?;
+    Ok(messages)
+}
+
+fn views(messages: &[FramesMessage]) -> Result<Vec<FrameView<'_>>, Error> {
+    let mut views = Vec::new();
+    for message in messages {
+        let explain = |e| message.explain(e);
+        let frames = message
+            .get()
+            .and_then(|root| root.get_frames())
+            .map_err(explain)?;
+        for frame in frames.iter() {
+            views.push(view(frame).map_err(explain)?);
+        }
+    }
+    Ok(views)
+}
+
+/// Prints how the frame labelled `index` changed from `old` to `new`, and
+/// returns the number of differences. Both are of the same kind.
+fn diff_frame(index: &str, old: &FrameView, new: &FrameView) -> usize {
+    let mut differences = 0;
+    for ((name, old), (_, new)) in old.fields.iter().zip(&new.fields) {
+        if old != new {
+            println!("frame {}: {} {} -> {}", index, name, old, new);
+            differences += 1;
+        }
+    }
+
+    let (old, new) = (old.data, new.data);
+    if old.len() != new.len() {
+        println!(
+            "frame {}: data is {} bytes -> {} bytes",
+            index,
+            old.len(),
+            new.len()
+        );
+        differences += 1;
+    }
+    let differing = old.iter().zip(new).filter(|(a, b)| a != b).count();
+    if let Some(first) = old.iter().zip(new).position(|(a, b)| a != b) {
+        println!(
+            "frame {}: data first differs at byte {} ({} of {} compared bytes differ)",
+            index,
+            first,
+            differing,
+            old.len().min(new.len())
+        );
+        differences += 1;
+    }
+    differences
+}
+
+/// Pairs up the frames of `old` and `new`: first by kind, stream and pts,
+/// so a frame inserted or removed shows up as just that, and then, among
+/// the frames left over in between, by kind alone, so a frame whose stream
+/// or pts changed shows up as that change.
+fn compare(old: &[FrameView], new: &[FrameView]) -> Vec<Step> {
+    let old_keys: Vec<_> = old.iter().map(FrameView::identity).collect();
+    let new_keys: Vec<_> = new.iter().map(FrameView::identity).collect();
+    pair_changed(align(&old_keys, &new_keys), |i, j| {
+        old[i].kind == new[j].kind
+    })
+}
+
+pub fn diff_main(args: &[String]) -> Result<(), Error> {
+    let mut input = InputOptions::default();
+    let mut paths = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            path if !path.starts_with("--") => paths.push(path.to_string()),
+            _ if input.parse_arg(arg, &mut args)? => {}
+            flag => return Err(args::unknown_option(flag)),
+        }
+    }
+    let (old_path, new_path) = match paths.as_slice() {
+        [old, new] => (old, new),
+        _ => return Err(Error::Usage("diff needs two inputs".to_string())),
+    };
+
+    let (old_messages, new_messages) = (read_all(&input, old_path)?, read_all(&input, new_path)?);
+    let (old, new) = (views(&old_messages)?, views(&new_messages)?);
+
+    let mut differences = 0;
+    for step in compare(&old, &new) {
+        match step {
+            Step::Matched(i, j) if i == j => {
+                differences += diff_frame(&format!("#{}", i), &old[i], &new[j])
+            }
+            Step::Matched(i, j) => {
+                differences += diff_frame(&format!("#{} (now #{})", i, j), &old[i], &new[j])
+            }
+            Step::Removed(i) => {
+                println!("frame #{}: removed ({})", i, old[i].summary());
+                differences += 1;
+            }
+            Step::Added(j) => {
+                println!("frame #{}: added ({})", j, new[j].summary());
+                differences += 1;
+            }
+        }
+    }
+
+    println!(
+        "{} differences between {} frames of {} and {} frames of {}",
+        differences,
+        old.len(),
+        old_path,
+        new.len(),
+        new_path
+    );
+    match differences {
+        0 => Ok(()),
+        n => Err(Error::Differences(n)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn align_reports_insertions_and_removals() {
+        use Step::*;
+        assert_eq!(
+            align(&[1, 2, 3], &[0, 1, 2, 3]),
+            [Added(0), Matched(0, 1), Matched(1, 2), Matched(2, 3)]
+        );
+        assert_eq!(
+            align(&[1, 2, 3, 4], &[1, 3, 5]),
+            [
+                Matched(0, 0),
+                Removed(1),
+                Matched(2, 1),
+                Added(2),
+                Removed(3)
+            ]
+        );
+        // Repeated identities pair up in order.
+        assert_eq!(align(&[7, 7], &[7]), [Matched(0, 0), Removed(1)]);
+    }
+
+    #[test]
+    fn pair_changed_pairs_leftovers_between_matches() {
+        use Step::*;
+        let steps = vec![
+            Added(0),
+            Removed(0),
+            Matched(1, 1),
+            Removed(2),
+            Added(2),
+            Added(3),
+        ];
+        assert_eq!(
+            pair_changed(steps, |_, _| true),
+            [Matched(0, 0), Matched(1, 1), Matched(2, 2), Added(3)]
+        );
+        let steps = vec![Added(0), Removed(0), Removed(1)];
+        assert_eq!(
+            pair_changed(steps, |i, _| i == 1),
+            [Matched(1, 0), Removed(0)]
+        );
+    }
+
+    fn audio<'a>(stream: u32, pts: &str, data: &'a [u8]) -> FrameView<'a> {
+        FrameView {
+            kind: "audio".to_string(),
+            fields: vec![
+                ("streamId", stream.to_string()),
+                ("pts", pts.to_string()),
+                ("channels", "2".to_string()),
+                ("numSamples", "4".to_string()),
+            ],
+            data,
+        }
+    }
+
+    #[test]
+    fn a_changed_pts_is_a_change_not_a_removal() {
+        let old = [audio(1, "0", &[1, 2]), audio(1, "1/100", &[3, 4])];
+        let new = [audio(1, "0", &[1, 2]), audio(1, "2/100", &[3, 4])];
+        let steps = compare(&old, &new);
+        assert_eq!(steps, [Step::Matched(0, 0), Step::Matched(1, 1)]);
+        assert_eq!(diff_frame("#1", &old[1], &new[1]), 1);
+    }
+
+    #[test]
+    fn a_changed_stream_still_compares_payloads() {
+        let old = [audio(69, "0", &[1, 2]), audio(69, "1/100", &[3, 4])];
+        let new = [audio(1, "0", &[1, 2]), audio(1, "1/100", &[3, 5])];
+        assert_eq!(
+            compare(&old, &new),
+            [Step::Matched(0, 0), Step::Matched(1, 1)]
+        );
+        assert_eq!(diff_frame("#0", &old[0], &new[0]), 1);
+        assert_eq!(diff_frame("#1", &old[1], &new[1]), 2);
+    }
+}
@@ -0,0 +1,146 @@
+//! The errors a subcommand can fail with, and the exit code for each.
+
+use crate::reader::ReadError;
+use std::fmt;
+use std::fs;
+use std::io;
+
+#[derive(Debug)]
+pub enum Error {
+    /// Bad command line: unknown command or option, bad option value.
+    Usage(String),
+    MissingFile(String),
+    Io(io::Error),
+    /// The input was read fine, but holds no frames at all.
+    NoFrames(String),
+    /// An enumerant or union variant this build does not know about.
+    NotInSchema(u16),
+    /// Frame `index` decoded fine, but its contents are inconsistent.
+    BadFrame {
+        index: usize,
+        reason: String,
+    },
+    /// Two `mux` inputs hold frames of the same stream id.
+    StreamCollision {
+        stream: u32,
+        first: String,
+        second: String,
+    },
+    /// `diff` found this many differences.
+    Differences(usize),
+    /// The index of this file was made before the file last changed.
+    StaleIndex(String),
+    /// `validate` found this many violations.
+    Invalid(usize),
+    Decode(capnp::Error),
+    /// `from-json` input that is not valid JSON for a frames file.
+    BadJson {
+        path: String,
+        error: serde_json::Error,
+    },
+    Read(ReadError),
+    Rpc(capnp::Error),
+    /// Sending or receiving frames over TCP failed.
+    Stream(capnp::Error),
+    ScenariosFailed {
+        failed: usize,
+        total: usize,
+    },
+}
+
+impl Error {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::ScenariosFailed { .. } => 1,
+            Error::Usage(_) => 2,
+            Error::MissingFile(_) => 3,
+            Error::Io(_) => 4,
+            Error::NoFrames(_) => 5,
+            Error::NotInSchema(_) => 6,
+            Error::Decode(_) | Error::Read(_) | Error::BadJson { .. } => 7,
+            Error::Rpc(_) => 8,
+            Error::BadFrame { .. } => 9,
+            Error::Invalid(_) => 10,
+            Error::StreamCollision { .. } => 11,
+            Error::StaleIndex(_) => 12,
+            Error::Differences(_) => 13,
+            Error::Stream(_) => 14,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Usage(message) => write!(f, "{}", message),
+            Error::MissingFile(path) => write!(f, "{}: no such file", path),
+            Error::Io(e) => write!(f, "{}", e),
+            Error::NoFrames(path) => write!(f, "{} holds no frames", path),
+            Error::NotInSchema(n) => write!(
+                f,
+                "unknown enumerant or union variant {} (written with a newer schema?)",
+                n
+            ),
+            Error::Decode(e) => write!(f, "could not decode message: {}", e.description),
+            Error::Read(e) => write!(f, "{}", e),
+            Error::BadJson { path, error } => write!(f, "{}: {}", path, error),
+            Error::Rpc(e) => write!(f, "RPC failed: {}", e.description),
+            Error::Stream(e) => write!(f, "streaming failed: {}", e.description),
+            Error::BadFrame { index, reason } => write!(f, "frame #{}: {}", index, reason),
+            Error::Invalid(n) => write!(f, "{} violations found", n),
+            Error::Differences(n) => write!(f, "{} differences found", n),
+            Error::StaleIndex(path) => {
+                write!(f, "{} changed since it was indexed (run index again)", path)
+            }
+            Error::StreamCollision {
+                stream,
+                first,
+                second,
+            } => write!(
+                f,
+                "stream {} is in both {} and {} (use --remap to tell them apart)",
+                stream, first, second
+            ),
+            Error::ScenariosFailed { failed, total } => {
+                write!(f, "{} of {} scenarios failed", failed, total)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Error {
+        Error::Io(e)
+    }
+}
+
+impl From<capnp::Error> for Error {
+    fn from(e: capnp::Error) -> Error {
+        Error::Decode(e)
+    }
+}
+
+impl From<capnp::NotInSchema> for Error {
+    fn from(capnp::NotInSchema(n): capnp::NotInSchema) -> Error {
+        Error::NotInSchema(n)
+    }
+}
+
+impl From<ReadError> for Error {
+    fn from(e: ReadError) -> Error {
+        match e {
+            ReadError::Io(e) => Error::Io(e),
+            e => Error::Read(e),
+        }
+    }
+}
+
+/// Opens an input file, telling a missing file apart from other I/O errors.
+pub fn open(path: &str) -> Result<fs::File, Error> {
+    fs::File::open(path).map_err(|e| match e.kind() {
+        io::ErrorKind::NotFound => Error::MissingFile(path.to_string()),
+        _ => Error::Io(e),
+    })
+}
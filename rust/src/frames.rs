@@ -0,0 +1,149 @@
+use crate::args::{self, InputOptions};
+use crate::error::Error;
+use crate::frames_capnp::{audio_frame, frame, rational, video_frame};
+use crate::mapped::MappedFrames;
+use crate::rational::Rational;
+use crate::reader::{Encoding, FramesMessage, ReadError};
+use capnp::message::ReaderSegments;
+use std::convert::TryFrom;
+
+fn pts_to_string(pts: rational::Reader) -> String {
+    let raw = format!("{}/{}", pts.get_num(), pts.get_den());
+    match Rational::try_from(pts).and_then(|pts| Ok((pts, pts.to_duration()?))) {
+        Ok((pts, duration)) => format!("{} ({} = {:?})", raw, pts, duration),
+        Err(e) => format!("{} (invalid: {})", raw, e),
+    }
+}
+
+pub fn pixel_format_name(format: Result<video_frame::PixelFormat, capnp::NotInSchema>) -> String {
+    match format {
+        Ok(video_frame::PixelFormat::Rgba) => "rgba".to_string(),
+        Err(capnp::NotInSchema(n)) => format!("unknown ({})", n),
+    }
+}
+
+pub fn dump_audio_frame(af: audio_frame::Reader) -> capnp::Result<()> {
+    println!("  stream ID: {}", af.get_stream_id());
+    println!("  pts: {}", pts_to_string(af.get_pts()?));
+    println!("  channels: {}", af.get_channels());
+    println!("  samples: {}", af.get_num_samples());
+    println!("  data: {} bytes", af.get_data()?.len());
+    Ok(())
+}
+
+pub fn dump_video_frame(vf: video_frame::Reader) -> capnp::Result<()> {
+    println!("  stream ID: {}", vf.get_stream_id());
+    println!("  pts: {}", pts_to_string(vf.get_pts()?));
+    println!("  size: {}x{}", vf.get_width(), vf.get_height());
+    println!(
+        "  pixel format: {}",
+        pixel_format_name(vf.get_pixel_format())
+    );
+    println!("  data: {} bytes", vf.get_data()?.len());
+    Ok(())
+}
+
+/// Running totals across every message of a file.
+#[derive(Default)]
+pub struct Counts {
+    frames: usize,
+    audio: usize,
+    video: usize,
+    unknown: usize,
+}
+
+impl Counts {
+    /// Prints the totals, for frames that came in `groups` of `unit`.
+    pub fn print(&self, groups: usize, unit: &str) {
+        println!(
+            "{} frames in {} {} ({} audio, {} video, {} unknown)",
+            self.frames, groups, unit, self.audio, self.video, self.unknown
+        );
+    }
+}
+
+pub fn dump_frame(frame: frame::Reader, counts: &mut Counts) -> capnp::Result<()> {
+    let i = counts.frames;
+    match frame.which() {
+        Ok(frame::AudioFrame(af)) => {
+            println!("frame #{}: audio", i);
+            dump_audio_frame(af?)?;
+            counts.audio += 1;
+        }
+        Ok(frame::VideoFrame(vf)) => {
+            println!("frame #{}: video", i);
+            dump_video_frame(vf?)?;
+            counts.video += 1;
+        }
+        Err(capnp::NotInSchema(n)) => {
+            println!("frame #{}: unknown variant {}", i, n);
+            counts.unknown += 1;
+        }
+    }
+    counts.frames += 1;
+    Ok(())
+}
+
+pub fn dump_frames<S: ReaderSegments>(
+    message: &FramesMessage<S>,
+    counts: &mut Counts,
+) -> capnp::Result<()> {
+    let frames = message.get()?.get_frames()?;
+    println!("message #{}: {} frames", message.index(), frames.len());
+
+    for frame in frames.iter() {
+        dump_frame(frame, counts)?;
+    }
+    Ok(())
+}
+
+/// Dumps every message, and returns how many there were.
+fn dump_messages<S, I>(messages: I, counts: &mut Counts) -> Result<usize, Error>
+where
+    S: ReaderSegments,
+    I: Iterator<Item = Result<FramesMessage<S>, ReadError>>,
+{
+    let mut n = 0;
+    for message in messages {
+        let message = message?;
+        dump_frames(&message, counts).map_err(|e| message.explain(e))?;
+        n += 1;
+    }
+    Ok(n)
+}
+
+pub fn frames_main(args: &[String]) -> Result<(), Error> {
+    let mut input = InputOptions::default();
+    let mut mmap = false;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--mmap" => mmap = true,
+            _ if input.parse_arg(arg, &mut args)? => {}
+            flag => return Err(args::unknown_option(flag)),
+        }
+    }
+
+    let mut counts = Counts::default();
+    let (messages, encoding) = if mmap {
+        if input.encoding == Some(Encoding::Packed) {
+            return Err(Error::Usage("--mmap only reads unpacked files".to_string()));
+        }
+        let mapped = MappedFrames::open(&input.path)?;
+        let messages = dump_messages(mapped.messages(input.reader_options), &mut counts)?;
+        (messages, Some(Encoding::Unpacked).filter(|_| messages > 0))
+    } else {
+        let mut reader = input.open()?;
+        let messages = dump_messages(reader.by_ref(), &mut counts)?;
+        (messages, reader.encoding())
+    };
+
+    if counts.frames == 0 {
+        return Err(Error::NoFrames(input.path));
+    }
+    if let Some(encoding) = encoding {
+        println!("encoding: {}", encoding.name());
+    }
+    counts.print(messages, "messages");
+    Ok(())
+}
@@ -0,0 +1,335 @@
+//! Synthetic audio and video whose content can be checked by ear or eye,
+//! unlike random payloads. Frame `n` of a generator can be asked for in any
+//! order, and its pts follows from `n` alone.
+
+use crate::model::{AudioFrame, PixelFormat, VideoFrame};
+use crate::rational::Rational;
+use std::f64::consts::PI;
+use std::fmt;
+use std::str::FromStr;
+
+/// Why a generator cannot be made.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GeneratorError {
+    ZeroSampleRate,
+    SampleSize(u32),
+    ZeroFps,
+}
+
+impl fmt::Display for GeneratorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GeneratorError::ZeroSampleRate => write!(f, "the sample rate is zero"),
+            GeneratorError::SampleSize(size) => {
+                write!(f, "samples are {} bytes, not 1 to 4", size)
+            }
+            GeneratorError::ZeroFps => write!(f, "the frame rate is zero"),
+        }
+    }
+}
+
+impl std::error::Error for GeneratorError {}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Signal {
+    Silence,
+    /// A sine wave at this many Hz, at half of full scale.
+    Sine(f64),
+}
+
+/// Parses `silence`, `sine` (440 Hz) or `sine:FREQ`.
+impl FromStr for Signal {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Signal, String> {
+        match s {
+            "silence" => return Ok(Signal::Silence),
+            "sine" => return Ok(Signal::Sine(440.0)),
+            _ => {}
+        }
+        match s.strip_prefix("sine:").map(str::parse::<f64>) {
+            Some(Ok(frequency)) if frequency.is_finite() && frequency >= 0.0 => {
+                Ok(Signal::Sine(frequency))
+            }
+            _ => Err("expected silence, sine or sine:FREQ".to_string()),
+        }
+    }
+}
+
+pub struct AudioGenerator {
+    stream_id: u32,
+    signal: Signal,
+    sample_rate: u32,
+    channels: u16,
+    /// Samples per frame, per channel.
+    samples: u32,
+    /// Bytes per sample: 1 is unsigned, 2 to 4 are signed little-endian,
+    /// as in WAV files.
+    sample_size: u32,
+}
+
+impl AudioGenerator {
+    pub fn new(
+        stream_id: u32,
+        signal: Signal,
+        sample_rate: u32,
+        channels: u16,
+        samples: u32,
+        sample_size: u32,
+    ) -> Result<AudioGenerator, GeneratorError> {
+        if sample_rate == 0 {
+            return Err(GeneratorError::ZeroSampleRate);
+        }
+        if !(1..=4).contains(&sample_size) {
+            return Err(GeneratorError::SampleSize(sample_size));
+        }
+        Ok(AudioGenerator {
+            stream_id,
+            signal,
+            sample_rate,
+            channels,
+            samples,
+            sample_size,
+        })
+    }
+
+    fn sample(&self, i: u64) -> f64 {
+        match self.signal {
+            Signal::Silence => 0.0,
+            Signal::Sine(frequency) => {
+                0.5 * (2.0 * PI * frequency * i as f64 / f64::from(self.sample_rate)).sin()
+            }
+        }
+    }
+
+    /// Frame `n`, which starts at sample `n * samples`.
+    pub fn frame(&self, n: u32) -> AudioFrame {
+        let first = u64::from(n) * u64::from(self.samples);
+        let bytes = self.sample_size as usize;
+        let max = ((1u64 << (8 * bytes - 1)) - 1) as f64;
+
+        let mut data = Vec::with_capacity(self.samples as usize * self.channels as usize * bytes);
+        for i in first..first + u64::from(self.samples) {
+            let value = (self.sample(i) * max).round() as i64;
+            let value = if bytes == 1 { value + 128 } else { value };
+            for _ in 0..self.channels {
+                data.extend_from_slice(&value.to_le_bytes()[..bytes]);
+            }
+        }
+        AudioFrame {
+            stream_id: self.stream_id,
+            pts: Rational::new(first as i64, i64::from(self.sample_rate))
+                .expect("the sample rate was checked in new"),
+            num_samples: self.samples,
+            channels: self.channels,
+            data,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Pattern {
+    /// Seven vertical bars: white, yellow, cyan, green, magenta, red, blue.
+    Bars,
+    /// Red rising left to right, green top to bottom.
+    Gradient,
+    /// A white square on black, moving one pixel right and down per frame
+    /// and wrapping around.
+    MovingBox,
+}
+
+impl FromStr for Pattern {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Pattern, String> {
+        match s {
+            "bars" => Ok(Pattern::Bars),
+            "gradient" => Ok(Pattern::Gradient),
+            "box" => Ok(Pattern::MovingBox),
+            _ => Err("expected bars, gradient or box".to_string()),
+        }
+    }
+}
+
+const BARS: [[u8; 3]; 7] = [
+    [255, 255, 255],
+    [255, 255, 0],
+    [0, 255, 255],
+    [0, 255, 0],
+    [255, 0, 255],
+    [255, 0, 0],
+    [0, 0, 255],
+];
+
+/// `i` of `0..n` scaled to `0..=255`.
+fn ramp(i: u32, n: u32) -> u8 {
+    if n < 2 {
+        0
+    } else {
+        (u64::from(i) * 255 / u64::from(n - 1)) as u8
+    }
+}
+
+pub struct VideoGenerator {
+    stream_id: u32,
+    pattern: Pattern,
+    width: u32,
+    height: u32,
+    fps: u32,
+}
+
+impl VideoGenerator {
+    pub fn new(
+        stream_id: u32,
+        pattern: Pattern,
+        width: u32,
+        height: u32,
+        fps: u32,
+    ) -> Result<VideoGenerator, GeneratorError> {
+        if fps == 0 {
+            return Err(GeneratorError::ZeroFps);
+        }
+        Ok(VideoGenerator {
+            stream_id,
+            pattern,
+            width,
+            height,
+            fps,
+        })
+    }
+
+    fn pixel(&self, n: u32, x: u32, y: u32) -> [u8; 3] {
+        match self.pattern {
+            Pattern::Bars => BARS[(u64::from(x) * 7 / u64::from(self.width)) as usize],
+            Pattern::Gradient => [ramp(x, self.width), ramp(y, self.height), 0],
+            Pattern::MovingBox => {
+                let size = (self.width.min(self.height) / 4).max(1);
+                let (left, top) = (
+                    n % (self.width.saturating_sub(size) + 1),
+                    n % (self.height.saturating_sub(size) + 1),
+                );
+                if (left..left + size).contains(&x) && (top..top + size).contains(&y) {
+                    [255, 255, 255]
+                } else {
+                    [0, 0, 0]
+                }
+            }
+        }
+    }
+
+    /// Frame `n`, shown at `n / fps`.
+    pub fn frame(&self, n: u32) -> VideoFrame {
+        let mut data = Vec::with_capacity(self.width as usize * self.height as usize * 4);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                data.extend_from_slice(&self.pixel(n, x, y));
+                data.push(255);
+            }
+        }
+        VideoFrame {
+            stream_id: self.stream_id,
+            pts: Rational::new(i64::from(n), i64::from(self.fps))
+                .expect("the frame rate was checked in new"),
+            width: self.width,
+            height: self.height,
+            pixel_format: PixelFormat::Rgba,
+            data,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn audio(signal: Signal, sample_size: u32) -> AudioGenerator {
+        AudioGenerator::new(69, signal, 3000, 2, 50, sample_size).unwrap()
+    }
+
+    fn video(pattern: Pattern, width: u32, height: u32) -> VideoGenerator {
+        VideoGenerator::new(70, pattern, width, height, 60).unwrap()
+    }
+
+    #[test]
+    fn rejects_what_it_cannot_generate() {
+        assert_eq!(
+            AudioGenerator::new(69, Signal::Silence, 0, 2, 50, 2).err(),
+            Some(GeneratorError::ZeroSampleRate)
+        );
+        assert_eq!(
+            AudioGenerator::new(69, Signal::Silence, 3000, 2, 50, 5).err(),
+            Some(GeneratorError::SampleSize(5))
+        );
+        assert_eq!(
+            VideoGenerator::new(70, Pattern::Bars, 4, 4, 0).err(),
+            Some(GeneratorError::ZeroFps)
+        );
+    }
+
+    #[test]
+    fn audio_pts_follow_the_sample_count() {
+        let generator = audio(Signal::Sine(440.0), 2);
+        assert_eq!(generator.frame(0).pts, Rational::new(0, 1).unwrap());
+        assert_eq!(generator.frame(3).pts, Rational::new(150, 3000).unwrap());
+        let frame = generator.frame(7);
+        assert_eq!(
+            (frame.stream_id, frame.num_samples, frame.channels),
+            (69, 50, 2)
+        );
+        assert_eq!(frame.data.len(), 50 * 2 * 2);
+    }
+
+    #[test]
+    fn silence_is_the_midpoint() {
+        assert!(audio(Signal::Silence, 2)
+            .frame(1)
+            .data
+            .iter()
+            .all(|b| *b == 0));
+        assert!(audio(Signal::Silence, 1)
+            .frame(1)
+            .data
+            .iter()
+            .all(|b| *b == 128));
+        assert_eq!(audio(Signal::Silence, 3).frame(0).data.len(), 50 * 2 * 3);
+    }
+
+    #[test]
+    fn sine_starts_at_zero_and_fills_every_channel() {
+        let data = audio(Signal::Sine(440.0), 2).frame(0).data;
+        assert_eq!(&data[..4], &[0, 0, 0, 0]);
+        assert_eq!(data[4..6], data[6..8]);
+        assert_ne!(&data[4..6], &[0, 0]);
+    }
+
+    #[test]
+    fn video_pts_follow_the_frame_rate() {
+        let generator = video(Pattern::Gradient, 4, 2);
+        assert_eq!(generator.frame(0).pts, Rational::new(0, 1).unwrap());
+        assert_eq!(generator.frame(30).pts, Rational::new(1, 2).unwrap());
+        let frame = generator.frame(1);
+        assert_eq!((frame.width, frame.height), (4, 2));
+        assert_eq!(frame.data.len(), 4 * 2 * 4);
+    }
+
+    #[test]
+    fn bars_are_in_order() {
+        let frame = video(Pattern::Bars, 7, 1).frame(0);
+        let colours: Vec<[u8; 3]> = frame
+            .data
+            .chunks(4)
+            .map(|pixel| [pixel[0], pixel[1], pixel[2]])
+            .collect();
+        assert_eq!(colours, BARS);
+        assert!(frame.data.chunks(4).all(|pixel| pixel[3] == 255));
+    }
+
+    #[test]
+    fn the_box_moves_one_pixel_per_frame() {
+        let white = |frame: &VideoFrame, x: usize, y: usize| frame.data[(y * 8 + x) * 4] == 255;
+        let generator = video(Pattern::MovingBox, 8, 8);
+        let (first, second) = (generator.frame(0), generator.frame(1));
+        assert!(white(&first, 0, 0) && !white(&first, 2, 2));
+        assert!(!white(&second, 0, 0) && white(&second, 2, 2));
+    }
+}
@@ -0,0 +1,247 @@
+use crate::args::{self, InputOptions};
+use crate::error::Error;
+use crate::frames_capnp::{frame, video_frame};
+use crate::model::{PixelFormat, VideoFrame};
+use crate::rational::Rational;
+use crate::reader::FramesMessage;
+use std::convert::TryFrom;
+use std::fs;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Copy)]
+enum ImageFormat {
+    Ppm,
+    Png,
+}
+
+impl ImageFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            ImageFormat::Ppm => "ppm",
+            ImageFormat::Png => "png",
+        }
+    }
+}
+
+struct ExportOptions {
+    input: InputOptions,
+    stream: Option<u32>,
+    /// Frames are exported when `from <= pts < to`.
+    from: Option<Rational>,
+    to: Option<Rational>,
+    format: ImageFormat,
+    output_dir: PathBuf,
+}
+
+impl ExportOptions {
+    fn parse(args: &[String]) -> Result<ExportOptions, Error> {
+        let mut opts = ExportOptions {
+            input: InputOptions::default(),
+            stream: None,
+            from: None,
+            to: None,
+            format: ImageFormat::Png,
+            output_dir: PathBuf::from("."),
+        };
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--stream" => opts.stream = Some(args::value(arg, args.next())?),
+                "--from" => opts.from = Some(args::value(arg, args.next())?),
+                "--to" => opts.to = Some(args::value(arg, args.next())?),
+                "--format" => {
+                    opts.format = match args::value::<String>(arg, args.next())?.as_str() {
+                        "ppm" => ImageFormat::Ppm,
+                        "png" => ImageFormat::Png,
+                        other => {
+                            return Err(Error::Usage(format!(
+                                "unknown image format {} (expected ppm or png)",
+                                other
+                            )))
+                        }
+                    }
+                }
+                "--output-dir" => opts.output_dir = args::value::<String>(arg, args.next())?.into(),
+                _ if opts.input.parse_arg(arg, &mut args)? => {}
+                flag => return Err(args::unknown_option(flag)),
+            }
+        }
+        Ok(opts)
+    }
+
+    fn wants(&self, vf: video_frame::Reader, index: usize) -> Result<bool, Error> {
+        if self
+            .stream
+            .is_some_and(|stream| stream != vf.get_stream_id())
+        {
+            return Ok(false);
+        }
+        let pts = Rational::try_from(vf.get_pts()?).map_err(|e| Error::BadFrame {
+            index,
+            reason: format!("invalid pts: {}", e),
+        })?;
+        let after_from = self.from.is_none_or(|from| pts >= from);
+        let before_to = self.to.is_none_or(|to| pts < to);
+        Ok(after_from && before_to)
+    }
+}
+
+fn write_ppm<W: Write>(w: &mut W, width: u32, height: u32, rgba: &[u8]) -> io::Result<()> {
+    write!(w, "P6\n{} {}\n255\n", width, height)?;
+    for pixel in rgba.chunks(4) {
+        w.write_all(&pixel[..3])?;
+    }
+    Ok(())
+}
+
+fn crc32(chunks: &[&[u8]]) -> u32 {
+    let mut crc = !0u32;
+    for &chunk in chunks {
+        for &byte in chunk {
+            crc ^= u32::from(byte);
+            for _ in 0..8 {
+                crc = if crc & 1 != 0 {
+                    (crc >> 1) ^ 0xEDB8_8320
+                } else {
+                    crc >> 1
+                };
+            }
+        }
+    }
+    !crc
+}
+
+fn write_png_chunk<W: Write>(w: &mut W, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+    w.write_all(&(data.len() as u32).to_be_bytes())?;
+    w.write_all(kind)?;
+    w.write_all(data)?;
+    w.write_all(&crc32(&[kind, data]).to_be_bytes())
+}
+
+/// Wraps `data` in a zlib stream made of stored (uncompressed) deflate
+/// blocks: bigger files, but no compression library needed.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x78, 0x01];
+    let mut blocks = data.chunks(0xffff).peekable();
+    if blocks.peek().is_none() {
+        out.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        out.push(if blocks.peek().is_none() { 1 } else { 0 });
+        let len = block.len() as u16;
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(&(!len).to_le_bytes());
+        out.extend_from_slice(block);
+    }
+
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + u32::from(byte)) % 65521;
+        b = (b + a) % 65521;
+    }
+    out.extend_from_slice(&(b << 16 | a).to_be_bytes());
+    out
+}
+
+fn write_png<W: Write>(w: &mut W, width: u32, height: u32, rgba: &[u8]) -> io::Result<()> {
+    w.write_all(b"\x89PNG\r\n\x1a\n")?;
+
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&width.to_be_bytes());
+    header.extend_from_slice(&height.to_be_bytes());
+    // 8 bits per channel, RGBA, deflate, adaptive filtering, no interlace.
+    header.extend_from_slice(&[8, 6, 0, 0, 0]);
+    write_png_chunk(w, b"IHDR", &header)?;
+
+    // Every scanline starts with its filter type, 0 for none.
+    let mut scanlines = Vec::with_capacity(rgba.len() + height as usize);
+    if width > 0 {
+        for row in rgba.chunks(width as usize * 4) {
+            scanlines.push(0);
+            scanlines.extend_from_slice(row);
+        }
+    }
+    write_png_chunk(w, b"IDAT", &zlib_stored(&scanlines))?;
+    write_png_chunk(w, b"IEND", &[])
+}
+
+fn export_frame(
+    opts: &ExportOptions,
+    vf: &VideoFrame,
+    index: usize,
+    path: &Path,
+) -> Result<(), Error> {
+    let PixelFormat::Rgba = vf.pixel_format;
+    let (width, height, data) = (vf.width, vf.height, &vf.data);
+    let expected = u64::from(width) * u64::from(height) * 4;
+    if data.len() as u64 != expected {
+        return Err(Error::BadFrame {
+            index,
+            reason: format!(
+                "data is {} bytes, expected {} ({}x{} rgba)",
+                data.len(),
+                expected,
+                width,
+                height
+            ),
+        });
+    }
+
+    let mut w = BufWriter::new(fs::File::create(path)?);
+    match opts.format {
+        ImageFormat::Ppm => write_ppm(&mut w, width, height, data)?,
+        ImageFormat::Png => write_png(&mut w, width, height, data)?,
+    }
+    w.flush()?;
+    Ok(())
+}
+
+fn export_message(
+    opts: &ExportOptions,
+    message: &FramesMessage,
+    next_index: &mut usize,
+    exported: &mut usize,
+) -> Result<(), Error> {
+    let frames = message
+        .get()
+        .and_then(|root| root.get_frames())
+        .map_err(|e| message.explain(e))?;
+    for frame in frames.iter() {
+        let index = *next_index;
+        *next_index += 1;
+
+        let vf = match frame.which() {
+            Ok(frame::VideoFrame(vf)) => vf.map_err(|e| message.explain(e))?,
+            _ => continue,
+        };
+        if !opts.wants(vf, index)? {
+            continue;
+        }
+        let path =
+            opts.output_dir
+                .join(format!("frame-{:05}.{}", exported, opts.format.extension()));
+        let vf = VideoFrame::try_from(vf).map_err(|e| e.at(message, index))?;
+        export_frame(opts, &vf, index, &path)?;
+        *exported += 1;
+    }
+    Ok(())
+}
+
+pub fn export_images_main(args: &[String]) -> Result<(), Error> {
+    let opts = ExportOptions::parse(args)?;
+    fs::create_dir_all(&opts.output_dir)?;
+
+    let (mut next_index, mut exported) = (0, 0);
+    for message in opts.input.open()? {
+        export_message(&opts, &message?, &mut next_index, &mut exported)?;
+    }
+    if exported == 0 {
+        return Err(Error::NoFrames(format!(
+            "{} (after filtering)",
+            opts.input.path
+        )));
+    }
+    println!("Wrote {} images to {}", exported, opts.output_dir.display());
+    Ok(())
+}
@@ -0,0 +1,242 @@
+//! Index sidecars, listing where every frame of a frames file is, so that
+//! a frame can be found by pts without reading the whole file.
+
+use crate::args::{self, InputOptions};
+use crate::demux::stream_and_pts;
+use crate::error::{self, Error};
+use crate::frames::{dump_audio_frame, dump_video_frame};
+use crate::frames_capnp::frame;
+use crate::index_capnp::frame_index;
+use crate::rational::Rational;
+use crate::reader::{Encoding, FramesMessage, FramesReader};
+use capnp::message::{Builder, HeapAllocator, ReaderOptions};
+use capnp::serialize;
+use std::convert::TryFrom;
+use std::fs;
+use std::io::{BufReader, Read, Seek, SeekFrom};
+
+struct Entry {
+    offset: u64,
+    message: u32,
+    position: u32,
+    stream: u32,
+    kind: frame_index::Kind,
+    num: u32,
+    den: u32,
+}
+
+fn index_message(message: &FramesMessage, entries: &mut Vec<Entry>) -> capnp::Result<()> {
+    for (position, frame) in message.get()?.get_frames()?.iter().enumerate() {
+        let kind = match frame.which() {
+            Ok(frame::AudioFrame(_)) => frame_index::Kind::Audio,
+            Ok(frame::VideoFrame(_)) => frame_index::Kind::Video,
+            Err(capnp::NotInSchema(_)) => continue,
+        };
+        if let Some((stream, pts)) = stream_and_pts(frame)? {
+            entries.push(Entry {
+                offset: message.offset(),
+                message: message.index() as u32,
+                position: position as u32,
+                stream,
+                kind,
+                num: pts.get_num(),
+                den: pts.get_den(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Reads every message of `reader`, which holds `file_size` bytes, and
+/// builds its `FrameIndex`. Pts are recorded as they are, valid or not.
+pub fn build_index<R: Read>(
+    reader: &mut FramesReader<R>,
+    file_size: u64,
+) -> Result<Builder<HeapAllocator>, Error> {
+    let mut entries = Vec::new();
+    for message in reader.by_ref() {
+        let message = message?;
+        index_message(&message, &mut entries).map_err(|e| message.explain(e))?;
+    }
+
+    let mut builder = Builder::new_default();
+    {
+        let mut index = builder.init_root::<frame_index::Builder>();
+        index.set_file_size(file_size);
+        index.set_packed(reader.encoding() == Some(Encoding::Packed));
+        let mut list = index.init_entries(entries.len() as u32);
+        for (i, entry) in entries.iter().enumerate() {
+            let mut out = list.reborrow().get(i as u32);
+            out.set_offset(entry.offset);
+            out.set_message(entry.message);
+            out.set_position(entry.position);
+            out.set_stream_id(entry.stream);
+            out.set_kind(entry.kind);
+            let mut pts = out.init_pts();
+            pts.set_num(entry.num);
+            pts.set_den(entry.den);
+        }
+    }
+    Ok(builder)
+}
+
+/// A frame found by `seek`, along with the one message read to get it.
+pub struct Found {
+    pub message: FramesMessage,
+    pub position: u32,
+}
+
+impl Found {
+    pub fn frame(&self) -> capnp::Result<frame::Reader<'_>> {
+        Ok(self.message.get()?.get_frames()?.get(self.position))
+    }
+}
+
+/// Picks the frame showing at `pts`: the last one, of `stream` if given,
+/// whose pts is not after it. The first in file order wins a tie.
+fn find_entry<'a>(
+    index: frame_index::Reader<'a>,
+    stream: Option<u32>,
+    pts: Rational,
+) -> capnp::Result<Option<frame_index::entry::Reader<'a>>> {
+    let mut best: Option<(Rational, frame_index::entry::Reader<'a>)> = None;
+    for entry in index.get_entries()?.iter() {
+        if stream.is_some_and(|stream| stream != entry.get_stream_id()) {
+            continue;
+        }
+        let entry_pts = match Rational::try_from(entry.get_pts()?) {
+            Ok(entry_pts) if entry_pts <= pts => entry_pts,
+            _ => continue,
+        };
+        if best.is_none_or(|(best_pts, _)| entry_pts > best_pts) {
+            best = Some((entry_pts, entry));
+        }
+    }
+    Ok(best.map(|(_, entry)| entry))
+}
+
+/// Finds the frame showing at `pts` with the index of the file at `path`,
+/// and reads only the message holding it. `None` if every frame comes later.
+pub fn seek(
+    path: &str,
+    index: frame_index::Reader,
+    options: ReaderOptions,
+    stream: Option<u32>,
+    pts: Rational,
+) -> Result<Option<Found>, Error> {
+    let mut f = error::open(path)?;
+    if f.metadata()?.len() != index.get_file_size() {
+        return Err(Error::StaleIndex(path.to_string()));
+    }
+    let entry = match find_entry(index, stream, pts)? {
+        Some(entry) => entry,
+        None => return Ok(None),
+    };
+
+    f.seek(SeekFrom::Start(entry.get_offset()))?;
+    let encoding = if index.get_packed() {
+        Encoding::Packed
+    } else {
+        Encoding::Unpacked
+    };
+    let mut reader = FramesReader::new(f, options, Some(encoding))
+        .starting_at(entry.get_message() as usize, entry.get_offset());
+    let message = match reader.next() {
+        Some(message) => message?,
+        None => return Err(Error::StaleIndex(path.to_string())),
+    };
+    let frames = message
+        .get()
+        .and_then(|root| root.get_frames())
+        .map_err(|e| message.explain(e))?;
+    if entry.get_position() >= frames.len() {
+        return Err(Error::StaleIndex(path.to_string()));
+    }
+    Ok(Some(Found {
+        message,
+        position: entry.get_position(),
+    }))
+}
+
+fn default_index_path(path: &str) -> String {
+    format!("{}.idx", path)
+}
+
+pub fn index_main(args: &[String]) -> Result<(), Error> {
+    let mut input = InputOptions::default();
+    let mut output = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--output" => output = Some(args::value::<String>(arg, args.next())?),
+            _ if input.parse_arg(arg, &mut args)? => {}
+            flag => return Err(args::unknown_option(flag)),
+        }
+    }
+    let output = output.unwrap_or_else(|| default_index_path(&input.path));
+
+    let file_size = error::open(&input.path)?.metadata()?.len();
+    let mut reader = input.open()?;
+    let builder = build_index(&mut reader, file_size)?;
+
+    let mut f = fs::File::create(&output)?;
+    serialize::write_message(&mut f, &builder)?;
+    let entries = builder
+        .get_root_as_reader::<frame_index::Reader>()?
+        .get_entries()?;
+    println!(
+        "Indexed {} frames of {} to {}",
+        entries.len(),
+        input.path,
+        output
+    );
+    Ok(())
+}
+
+pub fn seek_main(args: &[String]) -> Result<(), Error> {
+    let mut input = InputOptions::default();
+    let (mut index_path, mut stream, mut pts) = (None, None, None);
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--index" => index_path = Some(args::value::<String>(arg, args.next())?),
+            "--stream" => stream = Some(args::value(arg, args.next())?),
+            "--pts" => pts = Some(args::value::<Rational>(arg, args.next())?),
+            _ if input.parse_arg(arg, &mut args)? => {}
+            flag => return Err(args::unknown_option(flag)),
+        }
+    }
+    let pts = pts.ok_or_else(|| Error::Usage("--pts is required".to_string()))?;
+    let index_path = index_path.unwrap_or_else(|| default_index_path(&input.path));
+
+    let index = serialize::read_message(
+        &mut BufReader::new(error::open(&index_path)?),
+        input.reader_options,
+    )?;
+    let index = index.get_root::<frame_index::Reader>()?;
+    let found = match seek(&input.path, index, input.reader_options, stream, pts)? {
+        Some(found) => found,
+        None => {
+            return Err(Error::NoFrames(format!(
+                "{} (at or before {})",
+                input.path, pts
+            )))
+        }
+    };
+
+    let message = &found.message;
+    println!(
+        "message #{} at offset {}, frame {} of it",
+        message.index(),
+        message.offset(),
+        found.position
+    );
+    let dump = || -> capnp::Result<()> {
+        match found.frame()?.which()? {
+            frame::AudioFrame(af) => dump_audio_frame(af?),
+            frame::VideoFrame(vf) => dump_video_frame(vf?),
+        }
+    };
+    dump().map_err(|e| message.explain(e))?;
+    Ok(())
+}
@@ -0,0 +1,371 @@
+//! A JSON form of frames files, for reading and hand-editing fixtures.
+//!
+//! Field and variant names follow the schema, `Data` is base64, and a pts
+//! or `Data` pointer that was never set is `null`.
+//!
+//! The frames always come back the same, but their layout only does for
+//! files already in the layout `from-json` writes, which is that of
+//! `write`: such a file comes back byte for byte. Files written any other
+//! way, e.g. by `mux` or `pull`, come back with frames that `diff` finds
+//! no differences in, but not necessarily the same bytes.
+
+use crate::args::{self, InputOptions};
+use crate::error::{self, Error};
+use crate::frames_capnp::{audio_frame, frame, frames, rational, video_frame};
+use crate::reader::{Encoding, FramesMessage};
+use capnp::message::{Builder, HeapAllocator};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{self, BufReader, Write};
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JsonFile {
+    encoding: Encoding,
+    messages: Vec<JsonFrames>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JsonFrames {
+    frames: Vec<JsonFrame>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum JsonFrame {
+    AudioFrame(JsonAudioFrame),
+    VideoFrame(JsonVideoFrame),
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JsonRational {
+    num: u32,
+    den: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct JsonAudioFrame {
+    stream_id: u32,
+    pts: Option<JsonRational>,
+    num_samples: u32,
+    channels: u16,
+    #[serde(with = "base64_data")]
+    data: Option<Vec<u8>>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct JsonVideoFrame {
+    stream_id: u32,
+    pts: Option<JsonRational>,
+    width: u32,
+    height: u32,
+    pixel_format: JsonPixelFormat,
+    #[serde(with = "base64_data")]
+    data: Option<Vec<u8>>,
+}
+
+/// An enumerant by name, or by number if this build does not know it.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum JsonPixelFormat {
+    Known(KnownPixelFormat),
+    Unknown(u16),
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum KnownPixelFormat {
+    Rgba,
+}
+
+mod base64_data {
+    use base64::engine::general_purpose::STANDARD;
+    use base64::Engine;
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(data: &Option<Vec<u8>>, s: S) -> Result<S::Ok, S::Error> {
+        match data {
+            Some(data) => s.serialize_str(&STANDARD.encode(data)),
+            None => s.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Option<Vec<u8>>, D::Error> {
+        match Option::<String>::deserialize(d)? {
+            Some(text) => STANDARD.decode(text).map(Some).map_err(de::Error::custom),
+            None => Ok(None),
+        }
+    }
+}
+
+fn pts_to_json(has_pts: bool, pts: rational::Reader) -> Option<JsonRational> {
+    if has_pts {
+        Some(JsonRational {
+            num: pts.get_num(),
+            den: pts.get_den(),
+        })
+    } else {
+        None
+    }
+}
+
+fn audio_to_json(af: audio_frame::Reader) -> capnp::Result<JsonAudioFrame> {
+    Ok(JsonAudioFrame {
+        stream_id: af.get_stream_id(),
+        pts: pts_to_json(af.has_pts(), af.get_pts()?),
+        num_samples: af.get_num_samples(),
+        channels: af.get_channels(),
+        data: if af.has_data() {
+            Some(af.get_data()?.to_vec())
+        } else {
+            None
+        },
+    })
+}
+
+fn video_to_json(vf: video_frame::Reader) -> capnp::Result<JsonVideoFrame> {
+    Ok(JsonVideoFrame {
+        stream_id: vf.get_stream_id(),
+        pts: pts_to_json(vf.has_pts(), vf.get_pts()?),
+        width: vf.get_width(),
+        height: vf.get_height(),
+        pixel_format: match vf.get_pixel_format() {
+            Ok(video_frame::PixelFormat::Rgba) => JsonPixelFormat::Known(KnownPixelFormat::Rgba),
+            Err(capnp::NotInSchema(n)) => JsonPixelFormat::Unknown(n),
+        },
+        data: if vf.has_data() {
+            Some(vf.get_data()?.to_vec())
+        } else {
+            None
+        },
+    })
+}
+
+/// Converts one message. Frames of a kind this build does not know cannot
+/// be written out again, so they fail the conversion.
+fn message_to_json(message: &FramesMessage) -> Result<JsonFrames, Error> {
+    let frames = message
+        .get()
+        .and_then(|root| root.get_frames())
+        .map_err(|e| message.explain(e))?;
+    let mut json = Vec::with_capacity(frames.len() as usize);
+    for frame in frames.iter() {
+        json.push(match frame.which()? {
+            frame::AudioFrame(af) => {
+                JsonFrame::AudioFrame(af.and_then(audio_to_json).map_err(|e| message.explain(e))?)
+            }
+            frame::VideoFrame(vf) => {
+                JsonFrame::VideoFrame(vf.and_then(video_to_json).map_err(|e| message.explain(e))?)
+            }
+        });
+    }
+    Ok(JsonFrames { frames: json })
+}
+
+fn write_pts(mut builder: rational::Builder, pts: &JsonRational) {
+    builder.set_num(pts.num);
+    builder.set_den(pts.den);
+}
+
+fn write_audio(mut af: audio_frame::Builder, json: &JsonAudioFrame) {
+    af.set_stream_id(json.stream_id);
+    af.set_channels(json.channels);
+    af.set_num_samples(json.num_samples);
+    if let Some(pts) = &json.pts {
+        write_pts(af.reborrow().init_pts(), pts);
+    }
+    if let Some(data) = &json.data {
+        af.set_data(data);
+    }
+}
+
+fn write_video(mut vf: video_frame::Builder, json: &JsonVideoFrame) -> Result<(), Error> {
+    vf.set_stream_id(json.stream_id);
+    vf.set_width(json.width);
+    vf.set_height(json.height);
+    match json.pixel_format {
+        JsonPixelFormat::Known(KnownPixelFormat::Rgba) => {
+            vf.set_pixel_format(video_frame::PixelFormat::Rgba)
+        }
+        // The generated setter only takes known enumerants.
+        JsonPixelFormat::Unknown(n) => return Err(Error::NotInSchema(n)),
+    }
+    if let Some(pts) = &json.pts {
+        write_pts(vf.reborrow().init_pts(), pts);
+    }
+    if let Some(data) = &json.data {
+        vf.set_data(data);
+    }
+    Ok(())
+}
+
+fn message_from_json(json: &JsonFrames) -> Result<Builder<HeapAllocator>, Error> {
+    let mut message = Builder::new_default();
+    {
+        let mut list = message
+            .init_root::<frames::Builder>()
+            .init_frames(json.frames.len() as u32);
+        for (i, frame) in json.frames.iter().enumerate() {
+            let out = list.reborrow().get(i as u32);
+            match frame {
+                JsonFrame::AudioFrame(af) => write_audio(out.init_audio_frame(), af),
+                JsonFrame::VideoFrame(vf) => write_video(out.init_video_frame(), vf)?,
+            }
+        }
+    }
+    Ok(message)
+}
+
+pub fn to_json_main(args: &[String]) -> Result<(), Error> {
+    let mut input = InputOptions::default();
+    let mut output = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--output" => output = Some(args::value::<String>(arg, args.next())?),
+            _ if input.parse_arg(arg, &mut args)? => {}
+            flag => return Err(args::unknown_option(flag)),
+        }
+    }
+
+    let mut reader = input.open()?;
+    let mut messages = Vec::new();
+    for message in reader.by_ref() {
+        messages.push(message_to_json(&message?)?);
+    }
+    let file = JsonFile {
+        encoding: reader.encoding().unwrap_or(Encoding::Unpacked),
+        messages,
+    };
+
+    let json = serde_json::to_string_pretty(&file).map_err(io::Error::from)?;
+    match output {
+        Some(path) => fs::write(path, json + "\n")?,
+        None => println!("{}", json),
+    }
+    Ok(())
+}
+
+pub fn from_json_main(args: &[String]) -> Result<(), Error> {
+    let (mut input, mut output, mut encoding) = (None, "data.bin".to_string(), None);
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--output" => output = args::value(arg, args.next())?,
+            "--packed" => encoding = Some(Encoding::Packed),
+            "--unpacked" => encoding = Some(Encoding::Unpacked),
+            flag if flag.starts_with("--") => return Err(args::unknown_option(flag)),
+            path => input = Some(path.to_string()),
+        }
+    }
+    let input = input.ok_or_else(|| Error::Usage("from-json needs an input".to_string()))?;
+
+    let file: JsonFile =
+        serde_json::from_reader(BufReader::new(error::open(&input)?)).map_err(|error| {
+            Error::BadJson {
+                path: input.clone(),
+                error,
+            }
+        })?;
+    let encoding = encoding.unwrap_or(file.encoding);
+
+    let mut f = io::BufWriter::new(fs::File::create(&output)?);
+    for message in &file.messages {
+        encoding.write_message(&mut f, &message_from_json(message)?)?;
+    }
+    f.flush()?;
+    println!(
+        "Wrote {} messages to {} ({})",
+        file.messages.len(),
+        output,
+        encoding.name()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::{Pattern, Signal};
+    use crate::reader::FramesReader;
+    use crate::write::{build_frames, WriteOptions};
+    use capnp::message::ReaderOptions;
+
+    /// Takes every message of `bytes` through JSON text and back.
+    fn round_trip(bytes: &[u8], encoding: Encoding) -> Vec<u8> {
+        let mut out = Vec::new();
+        for message in FramesReader::new(bytes, ReaderOptions::new(), None) {
+            let json = serde_json::to_string(&message_to_json(&message.unwrap()).unwrap()).unwrap();
+            let json: JsonFrames = serde_json::from_str(&json).unwrap();
+            encoding
+                .write_message(&mut out, &message_from_json(&json).unwrap())
+                .unwrap();
+        }
+        out
+    }
+
+    fn written(opts: &WriteOptions) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        opts.encoding
+            .write_message(&mut bytes, &build_frames(opts).unwrap())
+            .unwrap();
+        bytes
+    }
+
+    #[test]
+    fn files_made_by_write_come_back_byte_for_byte() {
+        for &encoding in &[Encoding::Unpacked, Encoding::Packed] {
+            let random = || WriteOptions {
+                encoding,
+                audio_frames: 5,
+                video_frames: 3,
+                seed: 7,
+                ..WriteOptions::default()
+            };
+            let generated = WriteOptions {
+                signal: Some(Signal::Sine(440.0)),
+                pattern: Some(Pattern::Bars),
+                ..random()
+            };
+            for opts in &[random(), generated] {
+                let bytes = written(opts);
+                assert_eq!(round_trip(&bytes, encoding), bytes);
+            }
+        }
+    }
+
+    #[test]
+    fn unset_pointers_stay_unset() {
+        let json: JsonFrames = serde_json::from_str(
+            r#"{"frames": [
+                {"audioFrame": {"streamId": 1, "pts": null, "numSamples": 0,
+                                "channels": 1, "data": null}},
+                {"videoFrame": {"streamId": 2, "pts": {"num": 1, "den": 60},
+                                "width": 0, "height": 0, "pixelFormat": "rgba",
+                                "data": ""}}
+            ]}"#,
+        )
+        .unwrap();
+        let mut bytes = Vec::new();
+        Encoding::Unpacked
+            .write_message(&mut bytes, &message_from_json(&json).unwrap())
+            .unwrap();
+        assert_eq!(round_trip(&bytes, Encoding::Unpacked), bytes);
+
+        let message = FramesReader::new(&bytes[..], ReaderOptions::new(), None)
+            .next()
+            .unwrap()
+            .unwrap();
+        match message_to_json(&message).unwrap().frames.as_slice() {
+            [JsonFrame::AudioFrame(af), JsonFrame::VideoFrame(vf)] => {
+                assert!(af.pts.is_none() && af.data.is_none());
+                assert_eq!(vf.data.as_deref(), Some(&[][..]));
+            }
+            _ => panic!("expected an audio and a video frame"),
+        }
+    }
+}
@@ -0,0 +1,120 @@
+//! Reads unpacked frames files through a memory mapping, without copying:
+//! messages borrow their segments from the mapping, so `Data` fields are
+//! slices of the file and pages can be dropped again once read.
+
+use crate::error::{self, Error};
+use crate::reader::{le_u32, segment_table_words, Encoding, FramesMessage, ReadError};
+use capnp::message::ReaderOptions;
+use capnp::serialize::{self, SliceSegments};
+use capnp::Word;
+use memmap2::Mmap;
+
+pub struct MappedFrames {
+    map: Mmap,
+}
+
+impl MappedFrames {
+    /// Maps the file at `path`, which must not change while it is mapped.
+    /// Packed files cannot be read in place, so they are refused.
+    pub fn open(path: &str) -> Result<MappedFrames, Error> {
+        let f = error::open(path)?;
+        // Safety: the mapping is read-only, and like every reader of the
+        // file we rely on nobody truncating or rewriting it meanwhile.
+        let map = unsafe { Mmap::map(&f)? };
+        #[cfg(unix)]
+        map.advise(memmap2::Advice::Sequential)?;
+        if Encoding::detect(&map) == Encoding::Packed {
+            return Err(Error::Usage(format!(
+                "{} is packed, which cannot be memory-mapped",
+                path
+            )));
+        }
+        Ok(MappedFrames { map })
+    }
+
+    pub fn messages(&self, options: ReaderOptions) -> MappedMessages<'_> {
+        MappedMessages {
+            bytes: &self.map,
+            offset: 0,
+            index: 0,
+            options,
+            done: false,
+        }
+    }
+}
+
+/// Iterates over the messages of a `MappedFrames`, with the same errors
+/// and stopping rules as `FramesReader`.
+pub struct MappedMessages<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+    index: usize,
+    options: ReaderOptions,
+    done: bool,
+}
+
+impl<'a> MappedMessages<'a> {
+    fn read_next(&mut self) -> Result<FramesMessage<SliceSegments<'a>>, ReadError> {
+        let rest = &self.bytes[self.offset..];
+        let (index, offset) = (self.index, self.offset as u64);
+        let truncated = ReadError::Truncated { index, offset };
+
+        if rest.len() < 4 {
+            return Err(truncated);
+        }
+        let count = le_u32(rest) as usize + 1;
+        if count > 512 {
+            return Err(ReadError::Capnp(capnp::Error::failed(format!(
+                "too many segments: {}",
+                count
+            ))));
+        }
+        let words = segment_table_words(rest).ok_or(truncated)?;
+        if words > self.options.traversal_limit_in_words {
+            return Err(ReadError::TraversalLimit {
+                index,
+                words,
+                limit: self.options.traversal_limit_in_words,
+            });
+        }
+        // The segment table is padded to a whole number of words.
+        let len = (4 + 4 * count).div_ceil(8) * 8 + words as usize * 8;
+        let message = rest
+            .get(..len)
+            .ok_or(ReadError::Truncated { index, offset })?;
+
+        // Safety: the mapping starts on a page boundary and every message
+        // is a whole number of words long, so `message` is word-aligned.
+        let message = unsafe { Word::bytes_to_words(message) };
+        let message =
+            serialize::read_message_from_words(message, self.options).map_err(ReadError::Capnp)?;
+        self.offset += len;
+        Ok(FramesMessage::new(
+            message,
+            index,
+            offset,
+            words,
+            self.options,
+        ))
+    }
+}
+
+impl<'a> Iterator for MappedMessages<'a> {
+    type Item = Result<FramesMessage<SliceSegments<'a>>, ReadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || self.offset == self.bytes.len() {
+            return None;
+        }
+        match self.read_next() {
+            Ok(message) => {
+                self.index += 1;
+                Some(Ok(message))
+            }
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
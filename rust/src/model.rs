@@ -0,0 +1,237 @@
+//! Owned frames, for code that would rather not carry capnp readers and
+//! their lifetimes around, such as tests building expected values.
+//!
+//! Pts are normalized `Rational`s, so two frames compare equal whatever
+//! time base their pts were written in, and `write_into` writes them in
+//! lowest terms.
+
+use crate::error::Error;
+use crate::frames_capnp::{audio_frame, frame, video_frame};
+use crate::rational::{Rational, RationalError};
+use crate::reader::FramesMessage;
+use std::convert::TryFrom;
+use std::fmt;
+
+/// Why a frame could not be converted.
+#[derive(Debug)]
+pub enum FrameError {
+    Capnp(capnp::Error),
+    NotInSchema(u16),
+    Pts(RationalError),
+}
+
+impl fmt::Display for FrameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FrameError::Capnp(e) => write!(f, "{}", e.description),
+            FrameError::NotInSchema(n) => write!(f, "unknown enumerant or union variant {}", n),
+            FrameError::Pts(e) => write!(f, "invalid pts: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for FrameError {}
+
+impl FrameError {
+    /// Turns this into an `Error` naming frame `index` of `message`.
+    pub fn at(self, message: &FramesMessage, index: usize) -> Error {
+        match self {
+            FrameError::Capnp(e) => message.explain(e).into(),
+            FrameError::NotInSchema(n) => Error::NotInSchema(n),
+            e => Error::BadFrame {
+                index,
+                reason: e.to_string(),
+            },
+        }
+    }
+}
+
+impl From<capnp::Error> for FrameError {
+    fn from(e: capnp::Error) -> FrameError {
+        FrameError::Capnp(e)
+    }
+}
+
+impl From<capnp::NotInSchema> for FrameError {
+    fn from(capnp::NotInSchema(n): capnp::NotInSchema) -> FrameError {
+        FrameError::NotInSchema(n)
+    }
+}
+
+impl From<RationalError> for FrameError {
+    fn from(e: RationalError) -> FrameError {
+        FrameError::Pts(e)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AudioFrame {
+    pub stream_id: u32,
+    pub pts: Rational,
+    pub num_samples: u32,
+    pub channels: u16,
+    pub data: Vec<u8>,
+}
+
+/// `video_frame::PixelFormat`, which the generated code does not make
+/// `Debug`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PixelFormat {
+    Rgba,
+}
+
+impl From<video_frame::PixelFormat> for PixelFormat {
+    fn from(format: video_frame::PixelFormat) -> PixelFormat {
+        match format {
+            video_frame::PixelFormat::Rgba => PixelFormat::Rgba,
+        }
+    }
+}
+
+impl From<PixelFormat> for video_frame::PixelFormat {
+    fn from(format: PixelFormat) -> video_frame::PixelFormat {
+        match format {
+            PixelFormat::Rgba => video_frame::PixelFormat::Rgba,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct VideoFrame {
+    pub stream_id: u32,
+    pub pts: Rational,
+    pub width: u32,
+    pub height: u32,
+    pub pixel_format: PixelFormat,
+    pub data: Vec<u8>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Frame {
+    Audio(AudioFrame),
+    Video(VideoFrame),
+}
+
+impl<'a> TryFrom<audio_frame::Reader<'a>> for AudioFrame {
+    type Error = FrameError;
+
+    fn try_from(af: audio_frame::Reader<'a>) -> Result<AudioFrame, FrameError> {
+        Ok(AudioFrame {
+            stream_id: af.get_stream_id(),
+            pts: Rational::try_from(af.get_pts()?)?,
+            num_samples: af.get_num_samples(),
+            channels: af.get_channels(),
+            data: af.get_data()?.to_vec(),
+        })
+    }
+}
+
+impl<'a> TryFrom<video_frame::Reader<'a>> for VideoFrame {
+    type Error = FrameError;
+
+    fn try_from(vf: video_frame::Reader<'a>) -> Result<VideoFrame, FrameError> {
+        Ok(VideoFrame {
+            stream_id: vf.get_stream_id(),
+            pts: Rational::try_from(vf.get_pts()?)?,
+            width: vf.get_width(),
+            height: vf.get_height(),
+            pixel_format: vf.get_pixel_format()?.into(),
+            data: vf.get_data()?.to_vec(),
+        })
+    }
+}
+
+impl<'a> TryFrom<frame::Reader<'a>> for Frame {
+    type Error = FrameError;
+
+    fn try_from(frame: frame::Reader<'a>) -> Result<Frame, FrameError> {
+        match frame.which()? {
+            frame::AudioFrame(af) => Ok(Frame::Audio(AudioFrame::try_from(af?)?)),
+            frame::VideoFrame(vf) => Ok(Frame::Video(VideoFrame::try_from(vf?)?)),
+        }
+    }
+}
+
+impl AudioFrame {
+    /// Fails if the pts does not fit the `UInt32` fields of `Rational`.
+    pub fn write_into(&self, mut af: audio_frame::Builder) -> Result<(), RationalError> {
+        af.set_stream_id(self.stream_id);
+        af.set_channels(self.channels);
+        af.set_num_samples(self.num_samples);
+        self.pts.write(af.reborrow().init_pts())?;
+        af.set_data(&self.data);
+        Ok(())
+    }
+}
+
+impl VideoFrame {
+    /// Fails if the pts does not fit the `UInt32` fields of `Rational`.
+    pub fn write_into(&self, mut vf: video_frame::Builder) -> Result<(), RationalError> {
+        vf.set_stream_id(self.stream_id);
+        vf.set_width(self.width);
+        vf.set_height(self.height);
+        vf.set_pixel_format(self.pixel_format.into());
+        self.pts.write(vf.reborrow().init_pts())?;
+        vf.set_data(&self.data);
+        Ok(())
+    }
+}
+
+impl Frame {
+    pub fn write_into(&self, frame: frame::Builder) -> Result<(), RationalError> {
+        match self {
+            Frame::Audio(af) => af.write_into(frame.init_audio_frame()),
+            Frame::Video(vf) => vf.write_into(frame.init_video_frame()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use capnp::message::Builder;
+
+    fn round_trip(expected: &Frame) -> Frame {
+        let mut message = Builder::new_default();
+        let mut root = message.init_root::<frame::Builder>();
+        expected.write_into(root.reborrow()).unwrap();
+        Frame::try_from(root.into_reader()).unwrap()
+    }
+
+    #[test]
+    fn audio_frames_round_trip() {
+        let expected = Frame::Audio(AudioFrame {
+            stream_id: 69,
+            pts: Rational::new(3850, 3000).unwrap(),
+            num_samples: 2,
+            channels: 2,
+            data: vec![1, 2, 3, 4, 5, 6, 7, 8],
+        });
+        assert_eq!(round_trip(&expected), expected);
+    }
+
+    #[test]
+    fn video_frames_round_trip() {
+        let expected = Frame::Video(VideoFrame {
+            stream_id: 70,
+            pts: Rational::new(1, 60).unwrap(),
+            width: 1,
+            height: 2,
+            pixel_format: PixelFormat::Rgba,
+            data: vec![255, 0, 0, 255, 0, 0, 255, 255],
+        });
+        assert_eq!(round_trip(&expected), expected);
+    }
+
+    #[test]
+    fn a_zero_denominator_is_a_pts_error() {
+        let mut message = Builder::new_default();
+        let mut af = message.init_root::<audio_frame::Builder>();
+        af.set_stream_id(69);
+        af.reborrow().init_pts().set_num(1);
+        match AudioFrame::try_from(af.into_reader()) {
+            Err(FrameError::Pts(RationalError::ZeroDenominator)) => {}
+            other => panic!("expected a zero denominator, got {:?}", other),
+        }
+    }
+}
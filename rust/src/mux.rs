@@ -0,0 +1,195 @@
+//! Merging several frames files into one, in presentation order.
+
+use crate::args::{self, InputOptions};
+use crate::demux::{copy_frame, stream_and_pts};
+use crate::error::Error;
+use crate::frames_capnp::{frame, frames};
+use crate::rational::Rational;
+use crate::reader::{Encoding, FramesMessage};
+use capnp::message::{Builder, HeapAllocator};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fs;
+
+pub struct MuxInput {
+    /// Names the input in errors, usually its path.
+    pub name: String,
+    pub messages: Vec<FramesMessage>,
+    /// Stream ids to rewrite, from the input's id to the output's.
+    pub remap: HashMap<u32, u32>,
+}
+
+pub struct Muxed {
+    pub message: Builder<HeapAllocator>,
+    /// Frames of a kind this build does not know, which have no stream id.
+    pub skipped: usize,
+}
+
+/// One frame of the output, by where it sits in the inputs.
+struct Entry {
+    input: usize,
+    message: usize,
+    position: u32,
+    pts: Rational,
+    stream: u32,
+}
+
+fn index_input<'a>(
+    slot: usize,
+    input: &'a MuxInput,
+    owners: &mut HashMap<u32, (usize, &'a str)>,
+    entries: &mut Vec<Entry>,
+    skipped: &mut usize,
+) -> Result<(), Error> {
+    let mut next_index = 0;
+    for (message_slot, message) in input.messages.iter().enumerate() {
+        let frames = message
+            .get()
+            .and_then(|root| root.get_frames())
+            .map_err(|e| message.explain(e))?;
+        for (position, frame) in frames.iter().enumerate() {
+            let index = next_index;
+            next_index += 1;
+
+            let (stream, pts) = match stream_and_pts(frame).map_err(|e| message.explain(e))? {
+                Some(key) => key,
+                None => {
+                    *skipped += 1;
+                    continue;
+                }
+            };
+            let pts = Rational::try_from(pts).map_err(|e| Error::BadFrame {
+                index,
+                reason: format!("invalid pts: {} (in {})", e, input.name),
+            })?;
+            let stream = input.remap.get(&stream).copied().unwrap_or(stream);
+            let (owner, owner_name) = *owners.entry(stream).or_insert((slot, &input.name));
+            if owner != slot {
+                return Err(Error::StreamCollision {
+                    stream,
+                    first: owner_name.to_string(),
+                    second: input.name.clone(),
+                });
+            }
+            entries.push(Entry {
+                input: slot,
+                message: message_slot,
+                position: position as u32,
+                pts,
+                stream,
+            });
+        }
+    }
+    Ok(())
+}
+
+fn set_stream_id(frame: frame::Builder, stream: u32) -> capnp::Result<()> {
+    match frame.which()? {
+        frame::AudioFrame(af) => af?.set_stream_id(stream),
+        frame::VideoFrame(vf) => vf?.set_stream_id(stream),
+    }
+    Ok(())
+}
+
+/// Merges the frames of `inputs` into one message, sorted by pts. Frames
+/// with equal pts keep the order of the inputs, then their order within
+/// an input. A stream id may only come from one input, after remapping.
+pub fn mux(inputs: &[MuxInput]) -> Result<Muxed, Error> {
+    let (mut owners, mut entries, mut skipped) = (HashMap::new(), Vec::new(), 0);
+    for (slot, input) in inputs.iter().enumerate() {
+        index_input(slot, input, &mut owners, &mut entries, &mut skipped)?;
+    }
+    entries.sort_by_key(|entry| entry.pts);
+
+    let mut message = Builder::new_default();
+    {
+        let mut list = message
+            .init_root::<frames::Builder>()
+            .init_frames(entries.len() as u32);
+        for (i, entry) in entries.iter().enumerate() {
+            let frames = inputs[entry.input].messages[entry.message]
+                .get()?
+                .get_frames()?;
+            copy_frame(list.reborrow().get(i as u32), frames.get(entry.position))?;
+            set_stream_id(list.reborrow().get(i as u32), entry.stream)?;
+        }
+    }
+    Ok(Muxed { message, skipped })
+}
+
+/// Parses a `--remap` value, e.g. `69=1`.
+fn parse_remap(value: &str) -> Result<(u32, u32), Error> {
+    let bad = || {
+        Error::Usage(format!(
+            "invalid value {:?} for --remap: expected FROM=TO",
+            value
+        ))
+    };
+    let eq = value.find('=').ok_or_else(bad)?;
+    let from = value[..eq].parse().map_err(|_| bad())?;
+    let to = value[eq + 1..].parse().map_err(|_| bad())?;
+    Ok((from, to))
+}
+
+pub fn mux_main(args: &[String]) -> Result<(), Error> {
+    let mut options = InputOptions::default();
+    let mut output = "muxed.bin".to_string();
+    // `--remap` applies to the next input on the command line.
+    let (mut paths, mut remap) = (Vec::new(), HashMap::new());
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--output" => output = args::value(arg, args.next())?,
+            "--remap" => {
+                let (from, to) = parse_remap(&args::value::<String>(arg, args.next())?)?;
+                remap.insert(from, to);
+            }
+            path if !path.starts_with("--") => {
+                paths.push((path.to_string(), std::mem::take(&mut remap)));
+            }
+            _ if options.parse_arg(arg, &mut args)? => {}
+            flag => return Err(args::unknown_option(flag)),
+        }
+    }
+    if paths.is_empty() {
+        return Err(Error::Usage("mux needs at least one input".to_string()));
+    }
+    if !remap.is_empty() {
+        return Err(Error::Usage(
+            "--remap must come before the input it applies to".to_string(),
+        ));
+    }
+
+    let mut inputs = Vec::with_capacity(paths.len());
+    let mut encoding = None;
+    for (path, remap) in paths {
+        let mut reader = options.open_path(&path)?;
+        let messages = reader.by_ref().collect::<Result<Vec<_>, _>>()?;
+        encoding = encoding.or_else(|| reader.encoding());
+        inputs.push(MuxInput {
+            name: path,
+            messages,
+            remap,
+        });
+    }
+    let muxed = mux(&inputs)?;
+
+    // The output keeps the encoding of the first input.
+    let encoding = encoding.unwrap_or(Encoding::Unpacked);
+    let mut f = fs::File::create(&output)?;
+    encoding.write_message(&mut f, &muxed.message)?;
+    let frames = muxed
+        .message
+        .get_root_as_reader::<frames::Reader>()?
+        .get_frames()?;
+    println!(
+        "Muxed {} frames from {} inputs into {}",
+        frames.len(),
+        inputs.len(),
+        output
+    );
+    if muxed.skipped > 0 {
+        println!("skipped {} frames of unknown kinds", muxed.skipped);
+    }
+    Ok(())
+}
@@ -0,0 +1,223 @@
+//! The packed stream encoding (https://capnproto.org/encoding.html#packing).
+//!
+//! `capnp::serialize_packed` from capnp 0.9 forms past-the-end pointers with
+//! `slice::get_unchecked`, which debug builds of current toolchains abort on,
+//! so we pack and unpack the output of `capnp::serialize` ourselves.
+
+use std::io::{self, BufRead, Read};
+
+/// Packs an unpacked message (a whole number of words).
+pub fn pack(input: &[u8]) -> Vec<u8> {
+    debug_assert_eq!(input.len() % 8, 0);
+    let words: Vec<&[u8]> = input.chunks(8).collect();
+    let mut out = Vec::with_capacity(input.len() / 2);
+
+    let mut i = 0;
+    while i < words.len() {
+        let word = words[i];
+        let tag = word
+            .iter()
+            .enumerate()
+            .filter(|(_, &b)| b != 0)
+            .fold(0u8, |tag, (bit, _)| tag | 1 << bit);
+        out.push(tag);
+        out.extend(word.iter().filter(|&&b| b != 0));
+        i += 1;
+
+        if tag == 0x00 {
+            // Followed by the number of additional all-zero words.
+            let run = words[i..]
+                .iter()
+                .take(255)
+                .take_while(|w| w.iter().all(|&b| b == 0))
+                .count();
+            out.push(run as u8);
+            i += run;
+        } else if tag == 0xff {
+            // Followed by the number of words copied verbatim; like the C++
+            // implementation, keep going while packing would not save much.
+            let run = words[i..]
+                .iter()
+                .take(255)
+                .take_while(|w| w.iter().filter(|&&b| b == 0).count() < 2)
+                .count();
+            out.push(run as u8);
+            for w in &words[i..i + run] {
+                out.extend_from_slice(w);
+            }
+            i += run;
+        }
+    }
+    out
+}
+
+/// Unpacks a packed stream on the fly, one tag at a time, so that it never
+/// reads past the end of the message being decoded.
+pub struct PackedRead<R> {
+    inner: R,
+    pending: Vec<u8>,
+    pos: usize,
+}
+
+impl<R: BufRead> PackedRead<R> {
+    pub fn new(inner: R) -> PackedRead<R> {
+        PackedRead {
+            inner,
+            pending: Vec::new(),
+            pos: 0,
+        }
+    }
+
+    fn read_byte(&mut self) -> io::Result<Option<u8>> {
+        let byte = match self.inner.fill_buf()?.first() {
+            Some(&b) => b,
+            None => return Ok(None),
+        };
+        self.inner.consume(1);
+        Ok(Some(byte))
+    }
+
+    fn expect_byte(&mut self) -> io::Result<u8> {
+        self.read_byte()?.ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "premature end of packed input",
+            )
+        })
+    }
+
+    /// Decodes the next tag into `pending`; returns false at EOF.
+    fn decode_tag(&mut self) -> io::Result<bool> {
+        let tag = match self.read_byte()? {
+            Some(tag) => tag,
+            None => return Ok(false),
+        };
+        self.pending.clear();
+        self.pos = 0;
+
+        for bit in 0..8 {
+            let byte = if tag & 1 << bit != 0 {
+                self.expect_byte()?
+            } else {
+                0
+            };
+            self.pending.push(byte);
+        }
+
+        if tag == 0x00 {
+            let run = self.expect_byte()? as usize;
+            self.pending.resize(8 + run * 8, 0);
+        } else if tag == 0xff {
+            let run = self.expect_byte()? as usize;
+            self.pending.resize(8 + run * 8, 0);
+            self.inner.read_exact(&mut self.pending[8..])?;
+        }
+        Ok(true)
+    }
+}
+
+impl<R: BufRead> Read for PackedRead<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        if self.pos == self.pending.len() && !self.decode_tag()? {
+            return Ok(0);
+        }
+        let n = buf.len().min(self.pending.len() - self.pos);
+        buf[..n].copy_from_slice(&self.pending[self.pos..self.pos + n]);
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use capnp::message::{Builder, ReaderOptions};
+    use capnp::serialize;
+
+    fn unpack(packed: &[u8]) -> Vec<u8> {
+        let mut out = Vec::new();
+        PackedRead::new(packed).read_to_end(&mut out).unwrap();
+        out
+    }
+
+    fn check(unpacked: &[u8], packed: &[u8]) {
+        assert_eq!(pack(unpacked), packed);
+        assert_eq!(unpack(packed), unpacked);
+    }
+
+    #[test]
+    fn packs_the_examples_from_the_spec() {
+        check(
+            &[8, 0, 0, 0, 3, 0, 2, 0, 0x19, 0, 0, 0, 0xaa, 1, 0, 0],
+            &[0x51, 8, 3, 2, 0x31, 0x19, 0xaa, 1],
+        );
+        check(&[0; 8], &[0, 0]);
+        check(&[0; 16], &[0, 1]);
+    }
+
+    #[test]
+    fn packs_runs_of_zero_words() {
+        // A tag covers itself and up to 255 more words.
+        check(&[0; 256 * 8], &[0, 255]);
+        check(&[0; 300 * 8], &[0, 255, 0, 43]);
+    }
+
+    #[test]
+    fn copies_runs_of_dense_words() {
+        let mut unpacked = vec![1u8; 8];
+        unpacked.extend_from_slice(&[1, 2, 3, 4, 5, 6, 7, 0]);
+        unpacked.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0, 9]);
+        let mut packed = vec![0xff, 1, 1, 1, 1, 1, 1, 1, 1, 1];
+        packed.extend_from_slice(&[1, 2, 3, 4, 5, 6, 7, 0]);
+        packed.extend_from_slice(&[0x80, 9]);
+        check(&unpacked, &packed);
+    }
+
+    #[test]
+    fn splits_dense_runs_longer_than_255_words() {
+        let unpacked = vec![0xabu8; 300 * 8];
+        let packed = pack(&unpacked);
+        // Tag, word, count and 255 words; then the same for the other 44.
+        assert_eq!(packed.len(), (1 + 8 + 1 + 255 * 8) + (1 + 8 + 1 + 43 * 8));
+        assert_eq!(&packed[..1], &[0xff]);
+        assert_eq!(packed[9], 255);
+        assert_eq!(packed[10 + 255 * 8], 0xff);
+        assert_eq!(packed[10 + 255 * 8 + 9], 43);
+        assert_eq!(unpack(&packed), unpacked);
+    }
+
+    #[test]
+    fn rejects_truncated_input() {
+        let mut out = Vec::new();
+        let e = PackedRead::new(&[0x51, 8, 3][..])
+            .read_to_end(&mut out)
+            .unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::UnexpectedEof);
+        let e = PackedRead::new(&[0xff, 1, 1, 1, 1, 1, 1, 1, 1, 1, 2][..])
+            .read_to_end(&mut out)
+            .unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn reads_back_messages_one_after_the_other() {
+        let mut packed = Vec::new();
+        for text in &["first", "second"] {
+            let mut message = Builder::new_default();
+            message.set_root(*text).unwrap();
+            let mut words = Vec::new();
+            serialize::write_message(&mut words, &message).unwrap();
+            packed.extend(pack(&words));
+        }
+
+        let mut input = PackedRead::new(&packed[..]);
+        for text in &["first", "second"] {
+            let message = serialize::read_message(&mut input, ReaderOptions::new()).unwrap();
+            assert_eq!(message.get_root::<capnp::text::Reader>().unwrap(), *text);
+        }
+        assert_eq!(input.read(&mut [0; 8]).unwrap(), 0);
+    }
+}
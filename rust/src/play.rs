@@ -0,0 +1,284 @@
+//! Replays a frames file in real time: each frame is emitted when the wall
+//! clock reaches its `pts`, counted from the first frame played, so that
+//! downstream services see a captured session at the pace it was captured.
+
+use crate::args::{self, InputOptions};
+use crate::demux::stream_and_pts;
+use crate::error::Error;
+use crate::frames::{dump_frame, Counts};
+use crate::frames_capnp::{frame, frames};
+use crate::rational::Rational;
+use crate::reader::FramesMessage;
+use crate::recordings_capnp::frame_sink;
+use crate::rpc;
+use crate::subscribe::{Pusher, SlowPolicy};
+use capnp::message::Builder;
+use futures::future;
+use std::convert::TryFrom;
+use std::io;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+use tokio::net::TcpStream;
+use tokio::runtime::current_thread;
+use tokio::timer::Delay;
+
+/// Where `play` emits frames.
+enum Output {
+    Dump(Counts),
+    /// One `Frames` message per frame, as `stream-recv` expects.
+    Tcp(TcpStream),
+    Sink(Pusher),
+}
+
+impl Output {
+    /// Emits `frame`, which belongs to `message`.
+    fn emit(
+        &mut self,
+        runtime: &mut current_thread::Runtime,
+        message: &FramesMessage,
+        frame: frame::Reader,
+    ) -> Result<(), Error> {
+        match self {
+            Output::Dump(counts) => dump_frame(frame, counts).map_err(|e| message.explain(e))?,
+            Output::Tcp(stream) => {
+                let mut single = Builder::new_default();
+                single
+                    .init_root::<frames::Builder>()
+                    .init_frames(1)
+                    .set_with_caveats(0, frame)
+                    .map_err(|e| message.explain(e))?;
+                let write = capnp_futures::serialize::write_message(&mut *stream, single);
+                runtime.block_on(write).map_err(Error::Stream)?;
+            }
+            Output::Sink(pusher) => {
+                runtime
+                    .block_on(future::poll_fn(|| pusher.poll_ready()))
+                    .map_err(Error::Stream)?;
+                pusher.offer(frame).map_err(|e| message.explain(e))?;
+                if pusher.cut_off() {
+                    return Err(Error::Stream(capnp::Error::failed(
+                        "the sink fell behind and was cut off (--slow drop)".to_string(),
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Waits for what was emitted to be delivered, and prints a summary.
+    fn finish(
+        self,
+        runtime: &mut current_thread::Runtime,
+        played: u64,
+        passes: usize,
+    ) -> Result<(), Error> {
+        match self {
+            Output::Dump(counts) => counts.print(passes, "passes"),
+            Output::Tcp(stream) => {
+                runtime.block_on(tokio::io::shutdown(stream))?;
+                println!("Sent {} frames in {} passes", played, passes);
+            }
+            Output::Sink(mut pusher) => {
+                runtime
+                    .block_on(future::poll_fn(|| pusher.poll_answers()))
+                    .map_err(Error::Stream)?;
+                let mut request = pusher.sink().done_request();
+                request.get().set_dropped(pusher.dropped());
+                runtime
+                    .block_on(request.send().promise)
+                    .map_err(Error::Stream)?;
+                println!("Pushed {} frames in {} passes", pusher.pushed(), passes);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Maps `pts` to wall-clock deadlines.
+struct Clock {
+    /// When the frame at `base` is due.
+    origin: Instant,
+    base: Option<Rational>,
+    speed: f64,
+}
+
+impl Clock {
+    /// When the frame at `pts` is due. The first frame asked about sets the
+    /// base, unless one was given; frames before the base are due at once.
+    fn due(&mut self, pts: Rational) -> Result<Instant, Error> {
+        let base = *self.base.get_or_insert(pts);
+        self.after(pts.checked_sub(base).map_or(0.0, Rational::to_f64))
+    }
+
+    /// The instant `seconds` of media time after the origin, at this speed.
+    fn after(&self, seconds: f64) -> Result<Instant, Error> {
+        Duration::try_from_secs_f64(seconds.max(0.0) / self.speed)
+            .ok()
+            .and_then(|offset| self.origin.checked_add(offset))
+            .ok_or_else(|| {
+                Error::Usage(format!(
+                    "frames {}s in are due too far in the future at this --speed",
+                    seconds
+                ))
+            })
+    }
+
+    /// Sleeps until `due`, an hour at a time: the timer cannot wait for
+    /// much more than two years at once.
+    fn sleep_until(
+        &self,
+        runtime: &mut current_thread::Runtime,
+        due: Instant,
+    ) -> Result<(), Error> {
+        loop {
+            let now = Instant::now();
+            if due <= now {
+                return Ok(());
+            }
+            let wake = due.min(now + Duration::from_secs(3600));
+            runtime
+                .block_on(Delay::new(wake))
+                .map_err(io::Error::other)?;
+        }
+    }
+}
+
+/// Plays every frame of one pass over the file, and returns how many were
+/// played and when the last of them was due.
+fn play_pass(
+    input: &InputOptions,
+    range: (Option<Rational>, Option<Rational>),
+    clock: &mut Clock,
+    runtime: &mut current_thread::Runtime,
+    output: &mut Output,
+) -> Result<(u64, Option<Instant>), Error> {
+    let (start, end) = range;
+    let (mut index, mut played, mut last_due) = (0, 0, None);
+    let mut reader = input.open()?;
+    for message in reader.by_ref() {
+        let message = message?;
+        let frames = message
+            .get()
+            .and_then(|root| root.get_frames())
+            .map_err(|e| message.explain(e))?;
+        let first = index;
+        index += frames.len() as usize;
+        for (i, frame) in frames.iter().enumerate() {
+            // Frames of unknown kinds have no pts to be paced by.
+            let pts = match stream_and_pts(frame).map_err(|e| message.explain(e))? {
+                Some((_, pts)) => Rational::try_from(pts).map_err(|e| Error::BadFrame {
+                    index: first + i,
+                    reason: format!("invalid pts: {}", e),
+                })?,
+                None => continue,
+            };
+            if start.is_some_and(|start| pts < start) || end.is_some_and(|end| pts >= end) {
+                continue;
+            }
+
+            let due = clock.due(pts)?;
+            clock.sleep_until(runtime, due)?;
+            output.emit(runtime, &message, frame)?;
+            played += 1;
+            last_due = Some(due);
+        }
+    }
+    Ok((played, last_due))
+}
+
+pub fn play_main(args: &[String]) -> Result<(), Error> {
+    let mut input = InputOptions::default();
+    let (mut start, mut end) = (None, None);
+    let (mut speed, mut looping) = (1.0, false);
+    let (mut tcp, mut sink) = (None, None);
+    let (mut max_in_flight, mut slow) = (8, SlowPolicy::Pause);
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--speed" => speed = args::value(arg, args.next())?,
+            "--loop" => looping = true,
+            "--start" => start = Some(args::value::<Rational>(arg, args.next())?),
+            "--end" => end = Some(args::value::<Rational>(arg, args.next())?),
+            "--tcp" => tcp = Some(args::value::<SocketAddr>(arg, args.next())?),
+            "--sink" => sink = Some(args::value::<SocketAddr>(arg, args.next())?),
+            "--max-in-flight" => max_in_flight = args::value(arg, args.next())?,
+            "--slow" => slow = args::value(arg, args.next())?,
+            _ if input.parse_arg(arg, &mut args)? => {}
+            flag => return Err(args::unknown_option(flag)),
+        }
+    }
+    if !(speed > 0.0 && f64::is_finite(speed)) {
+        return Err(Error::Usage(format!(
+            "--speed must be a positive number, not {}",
+            speed
+        )));
+    }
+    if let (Some(start), Some(end)) = (start, end) {
+        if end <= start {
+            return Err(Error::Usage(format!(
+                "--end {} is not after --start {}",
+                end, start
+            )));
+        }
+    }
+    if max_in_flight == 0 {
+        return Err(Error::Usage(
+            "--max-in-flight must be at least 1".to_string(),
+        ));
+    }
+
+    let mut runtime = current_thread::Runtime::new()?;
+    let mut output = match (tcp, sink) {
+        (Some(_), Some(_)) => {
+            return Err(Error::Usage(
+                "--tcp and --sink cannot be used together".to_string(),
+            ))
+        }
+        (Some(addr), None) => {
+            println!("Connecting to {}", addr);
+            let stream = runtime.block_on(TcpStream::connect(&addr))?;
+            stream.set_nodelay(true)?;
+            Output::Tcp(stream)
+        }
+        (None, Some(addr)) => {
+            println!("Connecting to {}", addr);
+            let sink: frame_sink::Client = rpc::connect(&mut runtime, &addr)?;
+            Output::Sink(Pusher::new(sink, max_in_flight, slow))
+        }
+        (None, None) => Output::Dump(Counts::default()),
+    };
+
+    let mut clock = Clock {
+        origin: Instant::now(),
+        base: start,
+        speed,
+    };
+    let (mut played, mut passes) = (0, 0);
+    loop {
+        let (n, last_due) = play_pass(&input, (start, end), &mut clock, &mut runtime, &mut output)?;
+        passes += 1;
+        played += n;
+        let last_due = match last_due {
+            Some(due) => due,
+            None if start.is_some() || end.is_some() => {
+                return Err(Error::Usage(format!(
+                    "no frames of {} fall between --start and --end",
+                    input.path
+                )))
+            }
+            None => return Err(Error::NoFrames(input.path)),
+        };
+        if !looping {
+            break;
+        }
+        // The next pass picks up where this one ends: at --end if there is
+        // one, or else with its first frame due along with the last one.
+        clock.origin = match (clock.base, end) {
+            (Some(base), Some(end)) => {
+                clock.after(end.checked_sub(base).map_or(0.0, Rational::to_f64))?
+            }
+            _ => last_due,
+        };
+    }
+    output.finish(&mut runtime, played, passes)
+}
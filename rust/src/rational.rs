@@ -0,0 +1,285 @@
+//! Exact timestamps: `frames_capnp::Rational` as an owned, normalized value.
+
+use crate::frames_capnp::rational;
+use serde::{Serialize, Serializer};
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RationalError {
+    ZeroDenominator,
+    /// The value does not fit the target, e.g. a negative or huge value
+    /// written into the `UInt32` fields of a `Rational` struct.
+    OutOfRange,
+    Parse,
+}
+
+impl fmt::Display for RationalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RationalError::ZeroDenominator => write!(f, "zero denominator"),
+            RationalError::OutOfRange => write!(f, "value out of range"),
+            RationalError::Parse => write!(f, "expected num/den or decimal seconds"),
+        }
+    }
+}
+
+impl std::error::Error for RationalError {}
+
+fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        let t = a % b;
+        a = b;
+        b = t;
+    }
+    a
+}
+
+/// `num / den` seconds, always in lowest terms with `den > 0`, so that equal
+/// values compare and hash equal whatever time base they came in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rational {
+    num: i64,
+    den: i64,
+}
+
+impl Rational {
+    pub fn new(num: i64, den: i64) -> Result<Rational, RationalError> {
+        Rational::reduce(i128::from(num), i128::from(den))
+    }
+
+    fn reduce(num: i128, den: i128) -> Result<Rational, RationalError> {
+        if den == 0 {
+            return Err(RationalError::ZeroDenominator);
+        }
+        let sign = if (num < 0) != (den < 0) { -1 } else { 1 };
+        let (num, den) = (num.unsigned_abs(), den.unsigned_abs());
+        let divisor = gcd(num, den).max(1);
+        let num = i64::try_from(num / divisor).map_err(|_| RationalError::OutOfRange)?;
+        let den = i64::try_from(den / divisor).map_err(|_| RationalError::OutOfRange)?;
+        Ok(Rational {
+            num: sign * num,
+            den,
+        })
+    }
+
+    pub fn num(self) -> i64 {
+        self.num
+    }
+
+    pub fn den(self) -> i64 {
+        self.den
+    }
+
+    pub fn checked_add(self, other: Rational) -> Option<Rational> {
+        let (a, b) = (i128::from(self.den), i128::from(other.den));
+        Rational::reduce(i128::from(self.num) * b + i128::from(other.num) * a, a * b).ok()
+    }
+
+    pub fn checked_sub(self, other: Rational) -> Option<Rational> {
+        self.checked_add(Rational {
+            num: -other.num,
+            den: other.den,
+        })
+    }
+
+    /// Expresses this value in units of `1/den` seconds, rounding to the
+    /// nearest unit (halves away from zero).
+    pub fn rescale(self, den: u32) -> Result<i64, RationalError> {
+        if den == 0 {
+            return Err(RationalError::ZeroDenominator);
+        }
+        let scaled = i128::from(self.num) * i128::from(den);
+        let (quotient, remainder) = (scaled / i128::from(self.den), scaled % i128::from(self.den));
+        let rounded = if 2 * remainder.abs() >= i128::from(self.den) {
+            quotient + scaled.signum()
+        } else {
+            quotient
+        };
+        i64::try_from(rounded).map_err(|_| RationalError::OutOfRange)
+    }
+
+    /// Converts to a `Duration`, truncating to whole nanoseconds. Fails for
+    /// negative values.
+    pub fn to_duration(self) -> Result<Duration, RationalError> {
+        if self.num < 0 {
+            return Err(RationalError::OutOfRange);
+        }
+        let (num, den) = (self.num as u64, self.den as u64);
+        let nanos = u128::from(num % den) * 1_000_000_000 / u128::from(den);
+        Ok(Duration::new(num / den, nanos as u32))
+    }
+
+    pub fn to_f64(self) -> f64 {
+        self.num as f64 / self.den as f64
+    }
+
+    /// Writes this value into a `Rational` struct, in lowest terms.
+    pub fn write(self, mut builder: rational::Builder) -> Result<(), RationalError> {
+        let num = u32::try_from(self.num).map_err(|_| RationalError::OutOfRange)?;
+        let den = u32::try_from(self.den).map_err(|_| RationalError::OutOfRange)?;
+        builder.set_num(num);
+        builder.set_den(den);
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<rational::Reader<'a>> for Rational {
+    type Error = RationalError;
+
+    fn try_from(reader: rational::Reader<'a>) -> Result<Rational, RationalError> {
+        Rational::new(i64::from(reader.get_num()), i64::from(reader.get_den()))
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Rational) -> Ordering {
+        (i128::from(self.num) * i128::from(other.den))
+            .cmp(&(i128::from(other.num) * i128::from(self.den)))
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Rational) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}", self.num, self.den)
+    }
+}
+
+/// Serialized as its `Display` form, which keeps it exact.
+impl Serialize for Rational {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Parses `num/den` (e.g. `1/30`) or decimal seconds (e.g. `-1.25`).
+impl FromStr for Rational {
+    type Err = RationalError;
+
+    fn from_str(s: &str) -> Result<Rational, RationalError> {
+        if let Some(slash) = s.find('/') {
+            let num = s[..slash].parse().map_err(|_| RationalError::Parse)?;
+            let den = s[slash + 1..].parse().map_err(|_| RationalError::Parse)?;
+            return Rational::new(num, den);
+        }
+
+        let (int, frac) = match s.find('.') {
+            Some(dot) => (&s[..dot], &s[dot + 1..]),
+            None => (s, ""),
+        };
+        if frac.starts_with(|c: char| !c.is_ascii_digit()) {
+            return Err(RationalError::Parse);
+        }
+        let den = 10i64
+            .checked_pow(frac.len() as u32)
+            .ok_or(RationalError::OutOfRange)?;
+        let num = format!("{}{}", int, frac)
+            .parse()
+            .map_err(|_| RationalError::Parse)?;
+        Rational::new(num, den)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frames_capnp::rational;
+    use capnp::message::Builder;
+
+    fn r(num: i64, den: i64) -> Rational {
+        Rational::new(num, den).unwrap()
+    }
+
+    #[test]
+    fn normalizes_to_lowest_terms() {
+        assert_eq!((r(3850, 3000).num(), r(3850, 3000).den()), (77, 60));
+        assert_eq!(r(0, 3000), r(0, 1));
+        assert_eq!(r(2, 4), r(1, 2));
+        assert_eq!(Rational::new(1, 0), Err(RationalError::ZeroDenominator));
+    }
+
+    #[test]
+    fn keeps_the_sign_on_the_numerator() {
+        assert_eq!((r(1, -2).num(), r(1, -2).den()), (-1, 2));
+        assert_eq!(r(-1, -2), r(1, 2));
+        assert_eq!(r(-3, 6).to_string(), "-1/2");
+    }
+
+    #[test]
+    fn orders_by_value() {
+        assert!(r(1, 3) < r(1, 2));
+        assert!(r(-1, 2) < r(-1, 3));
+        assert_eq!(r(2, 6).cmp(&r(1, 3)), Ordering::Equal);
+        assert!(r(i64::MAX, 1) > r(i64::MAX - 1, 1));
+    }
+
+    #[test]
+    fn adds_and_subtracts_exactly() {
+        assert_eq!(r(1, 30).checked_add(r(1, 60)), Some(r(1, 20)));
+        assert_eq!(r(1, 60).checked_sub(r(1, 30)), Some(r(-1, 60)));
+        assert_eq!(r(i64::MAX, 1).checked_add(r(1, 1)), None);
+    }
+
+    #[test]
+    fn rescale_rounds_halves_away_from_zero() {
+        assert_eq!(r(1, 30).rescale(3000), Ok(100));
+        assert_eq!(r(1, 4).rescale(2), Ok(1));
+        assert_eq!(r(-1, 4).rescale(2), Ok(-1));
+        assert_eq!(r(1, 3).rescale(2), Ok(1));
+        assert_eq!(r(1, 5).rescale(2), Ok(0));
+        assert_eq!(r(-1, 5).rescale(2), Ok(0));
+        assert_eq!(r(1, 2).rescale(0), Err(RationalError::ZeroDenominator));
+        assert_eq!(r(i64::MAX, 1).rescale(2), Err(RationalError::OutOfRange));
+    }
+
+    #[test]
+    fn converts_to_duration() {
+        assert_eq!(r(77, 60).to_duration(), Ok(Duration::new(1, 283_333_333)));
+        assert_eq!(r(0, 1).to_duration(), Ok(Duration::from_secs(0)));
+        assert_eq!(r(-1, 2).to_duration(), Err(RationalError::OutOfRange));
+    }
+
+    #[test]
+    fn parses_fractions_and_decimals() {
+        assert_eq!("1/30".parse(), Ok(r(1, 30)));
+        assert_eq!("-1.25".parse(), Ok(r(-5, 4)));
+        assert_eq!(".5".parse(), Ok(r(1, 2)));
+        assert_eq!("2".parse(), Ok(r(2, 1)));
+        assert_eq!(
+            "1/0".parse::<Rational>(),
+            Err(RationalError::ZeroDenominator)
+        );
+        assert_eq!("1.-5".parse::<Rational>(), Err(RationalError::Parse));
+        assert_eq!("x/2".parse::<Rational>(), Err(RationalError::Parse));
+    }
+
+    #[test]
+    fn writes_only_what_fits_the_struct() {
+        let mut message = Builder::new_default();
+        let mut pts = message.init_root::<rational::Builder>();
+        r(3850, 3000).write(pts.reborrow()).unwrap();
+        assert_eq!(
+            (pts.reborrow().get_num(), pts.reborrow().get_den()),
+            (77, 60)
+        );
+        assert_eq!(
+            r(-1, 2).write(pts.reborrow()),
+            Err(RationalError::OutOfRange)
+        );
+        assert_eq!(
+            r(1 << 32, 1).write(pts.reborrow()),
+            Err(RationalError::OutOfRange)
+        );
+        let reader = pts.into_reader();
+        assert_eq!(Rational::try_from(reader), Ok(r(77, 60)));
+    }
+}
@@ -0,0 +1,463 @@
+//! Reads frames files made of any number of concatenated `Frames` messages.
+
+use crate::frames_capnp::frames;
+use crate::packed::{self, PackedRead};
+use capnp::message::{self, ReaderOptions, ReaderSegments};
+use capnp::serialize::{self, OwnedSegments};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Read, Write};
+
+/// The deepest pointer chain in a frames file: `Frames` -> `frames` ->
+/// `Frame` -> `audioFrame` -> `pts`.
+const FRAMES_NESTING_DEPTH: i32 = 5;
+
+/// Enough bytes to hold the largest segment table capnp accepts.
+const MAX_SEGMENT_TABLE_BYTES: usize = 4 + 4 * 512;
+
+pub fn le_u32(bytes: &[u8]) -> u32 {
+    u32::from(bytes[0])
+        | u32::from(bytes[1]) << 8
+        | u32::from(bytes[2]) << 16
+        | u32::from(bytes[3]) << 24
+}
+
+/// Sums the segment sizes announced by an unpacked segment table, if `header`
+/// holds all of it.
+pub fn segment_table_words(header: &[u8]) -> Option<u64> {
+    if header.len() < 4 {
+        return None;
+    }
+    let count = le_u32(header) as usize + 1;
+    let sizes = header.get(4..4 + 4 * count)?;
+    Some(sizes.chunks(4).map(|size| u64::from(le_u32(size))).sum())
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Encoding {
+    /// `capnp::serialize`: a segment table followed by the raw segments.
+    Unpacked,
+    /// The packed encoding: the same, with runs of zero bytes squeezed.
+    Packed,
+}
+
+impl Encoding {
+    /// Guesses the encoding of a stream from its first bytes.
+    ///
+    /// An unpacked stream starts with its segment count minus one, which
+    /// capnp caps at 512. A packed stream starts with a tag byte and the
+    /// non-zero bytes of the segment table, followed by the tag of the root
+    /// pointer, so bytes 2 and 3 are only both zero if the root is null.
+    pub fn detect(prefix: &[u8]) -> Encoding {
+        if prefix.len() < 4 {
+            return Encoding::Unpacked;
+        }
+        if le_u32(prefix) < 512 {
+            Encoding::Unpacked
+        } else {
+            Encoding::Packed
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Encoding::Unpacked => "unpacked",
+            Encoding::Packed => "packed",
+        }
+    }
+
+    pub fn write_message<W, A>(
+        self,
+        write: &mut W,
+        message: &capnp::message::Builder<A>,
+    ) -> io::Result<()>
+    where
+        W: Write,
+        A: capnp::message::Allocator,
+    {
+        self.write_segments(write, &message.get_segments_for_output())
+    }
+
+    /// Writes a message that was read rather than built, as is.
+    pub fn write_segments<W, S>(self, write: &mut W, segments: &S) -> io::Result<()>
+    where
+        W: Write,
+        S: ReaderSegments,
+    {
+        match self {
+            Encoding::Unpacked => serialize::write_message_segments(write, segments),
+            Encoding::Packed => {
+                let mut words = Vec::new();
+                serialize::write_message_segments(&mut words, segments)?;
+                write.write_all(&packed::pack(&words))
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ReadError {
+    /// The input ended partway through message `index`, which started
+    /// `offset` bytes into the input.
+    Truncated {
+        index: usize,
+        offset: u64,
+    },
+    /// Message `index` is `words` long, more than the traversal limit allows.
+    TraversalLimit {
+        index: usize,
+        words: u64,
+        limit: u64,
+    },
+    NestingLimit {
+        index: usize,
+        limit: i32,
+    },
+    Io(io::Error),
+    Capnp(capnp::Error),
+}
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReadError::Truncated { index, offset } => {
+                write!(f, "message #{} (at byte {}) is truncated", index, offset)
+            }
+            ReadError::TraversalLimit {
+                index,
+                words,
+                limit,
+            } => write!(
+                f,
+                "message #{} is {} words, over the traversal limit of {} words \
+                 (try --traversal-limit {})",
+                index, words, limit, words
+            ),
+            ReadError::NestingLimit { index, limit } => write!(
+                f,
+                "message #{} is nested deeper than the nesting limit of {} \
+                 (frames files need --nesting-limit {})",
+                index, limit, FRAMES_NESTING_DEPTH
+            ),
+            ReadError::Io(e) => write!(f, "{}", e),
+            ReadError::Capnp(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for ReadError {}
+
+/// Keeps track of how far into the input we are, and whether a read came
+/// back empty, so that a failed `read_message` can be told apart from a
+/// message that was cut short.
+struct Tracked<R> {
+    inner: BufReader<R>,
+    offset: u64,
+    hit_eof: bool,
+}
+
+impl<R: Read> Read for Tracked<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        if n == 0 && !buf.is_empty() {
+            self.hit_eof = true;
+        }
+        self.offset += n as u64;
+        Ok(n)
+    }
+}
+
+impl<R: Read> BufRead for Tracked<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        let buf = self.inner.fill_buf()?;
+        if buf.is_empty() {
+            self.hit_eof = true;
+        }
+        Ok(buf)
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.offset += amt as u64;
+        self.inner.consume(amt);
+    }
+}
+
+/// Keeps a copy of the first bytes read, which hold the segment table.
+struct Sniffed<R> {
+    inner: R,
+    header: Vec<u8>,
+}
+
+impl<R: Read> Read for Sniffed<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        let room = MAX_SEGMENT_TABLE_BYTES.saturating_sub(self.header.len());
+        self.header.extend_from_slice(&buf[..n.min(room)]);
+        Ok(n)
+    }
+}
+
+/// One `Frames` message, along with what is needed to explain a limit
+/// error raised while traversing it. Its segments are owned, unless it
+/// was read from a memory-mapped file.
+pub struct FramesMessage<S: ReaderSegments = OwnedSegments> {
+    message: message::Reader<S>,
+    index: usize,
+    offset: u64,
+    words: u64,
+    options: ReaderOptions,
+}
+
+impl<S: ReaderSegments> FramesMessage<S> {
+    /// Wraps message `index`, found `offset` bytes into its input, whose
+    /// segment table announced `words` words.
+    pub fn new(
+        message: message::Reader<S>,
+        index: usize,
+        offset: u64,
+        words: u64,
+        options: ReaderOptions,
+    ) -> FramesMessage<S> {
+        FramesMessage {
+            message,
+            index,
+            offset,
+            words,
+            options,
+        }
+    }
+
+    pub fn get(&self) -> capnp::Result<frames::Reader<'_>> {
+        self.message.get_root()
+    }
+
+    /// Position of this message in its stream, starting at 0.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Where this message starts in the input, in bytes.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    pub fn into_segments(self) -> S {
+        self.message.into_segments()
+    }
+
+    /// Turns an error from reading this message into a `ReadError`, naming
+    /// the limit that tripped, if any.
+    ///
+    /// capnp reports both limits as plain `Failed` errors, so the only way
+    /// to tell them apart is the description.
+    pub fn explain(&self, e: capnp::Error) -> ReadError {
+        if e.description == "read limit exceeded" {
+            ReadError::TraversalLimit {
+                index: self.index,
+                words: self.words,
+                limit: self.options.traversal_limit_in_words,
+            }
+        } else if e.description == "nesting limit exceeded"
+            || e.description.starts_with("Message is too deeply-nested")
+        {
+            ReadError::NestingLimit {
+                index: self.index,
+                limit: self.options.nesting_limit,
+            }
+        } else {
+            ReadError::Capnp(e)
+        }
+    }
+}
+
+/// Iterates over the `Frames` messages of a stream until EOF.
+///
+/// A stream that ends cleanly between two messages simply ends the
+/// iteration; one that ends in the middle of a message yields
+/// `ReadError::Truncated`. Iteration stops after the first error.
+pub struct FramesReader<R> {
+    input: Tracked<R>,
+    options: ReaderOptions,
+    encoding: Option<Encoding>,
+    index: usize,
+    done: bool,
+}
+
+impl<R: Read> FramesReader<R> {
+    /// With no `encoding`, it is detected from the first message.
+    pub fn new(read: R, options: ReaderOptions, encoding: Option<Encoding>) -> FramesReader<R> {
+        FramesReader {
+            input: Tracked {
+                inner: BufReader::new(read),
+                offset: 0,
+                hit_eof: false,
+            },
+            options,
+            encoding,
+            index: 0,
+            done: false,
+        }
+    }
+
+    /// For input that starts partway into a file, at message `index` and
+    /// `offset` bytes in, so that messages and errors say where they are.
+    pub fn starting_at(mut self, index: usize, offset: u64) -> FramesReader<R> {
+        self.index = index;
+        self.input.offset = offset;
+        self
+    }
+
+    /// The encoding in use, once it is known.
+    pub fn encoding(&self) -> Option<Encoding> {
+        self.encoding
+    }
+
+    fn read_next(&mut self) -> Result<Option<FramesMessage>, ReadError> {
+        let prefix = self.input.inner.fill_buf().map_err(ReadError::Io)?;
+        if prefix.is_empty() {
+            return Ok(None);
+        }
+        let encoding = *self
+            .encoding
+            .get_or_insert_with(|| Encoding::detect(prefix));
+
+        let offset = self.input.offset;
+        let (result, header) = match encoding {
+            Encoding::Unpacked => {
+                let mut input = Sniffed {
+                    inner: &mut self.input,
+                    header: Vec::new(),
+                };
+                let result = serialize::read_message(&mut input, self.options);
+                (result, input.header)
+            }
+            Encoding::Packed => {
+                let mut input = Sniffed {
+                    inner: PackedRead::new(&mut self.input),
+                    header: Vec::new(),
+                };
+                let result = serialize::read_message(&mut input, self.options);
+                (result, input.header)
+            }
+        };
+
+        let words = segment_table_words(&header).unwrap_or(0);
+        match result {
+            Ok(message) => Ok(Some(FramesMessage::new(
+                message,
+                self.index,
+                offset,
+                words,
+                self.options,
+            ))),
+            Err(_) if self.input.hit_eof => Err(ReadError::Truncated {
+                index: self.index,
+                offset,
+            }),
+            Err(_) if words > self.options.traversal_limit_in_words => {
+                Err(ReadError::TraversalLimit {
+                    index: self.index,
+                    words,
+                    limit: self.options.traversal_limit_in_words,
+                })
+            }
+            Err(e) => Err(ReadError::Capnp(e)),
+        }
+    }
+}
+
+impl<R: Read> Iterator for FramesReader<R> {
+    type Item = Result<FramesMessage, ReadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.read_next() {
+            Ok(Some(message)) => {
+                self.index += 1;
+                Some(Ok(message))
+            }
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use capnp::message::Builder;
+
+    fn frames_message(frames: u32) -> Builder<capnp::message::HeapAllocator> {
+        let mut message = Builder::new_default();
+        message.init_root::<frames::Builder>().init_frames(frames);
+        message
+    }
+
+    fn encode(encoding: Encoding, messages: &[u32]) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        for frames in messages {
+            encoding
+                .write_message(&mut bytes, &frames_message(*frames))
+                .unwrap();
+        }
+        bytes
+    }
+
+    #[test]
+    fn detects_the_encoding() {
+        assert_eq!(
+            Encoding::detect(&encode(Encoding::Unpacked, &[1])),
+            Encoding::Unpacked
+        );
+        assert_eq!(
+            Encoding::detect(&encode(Encoding::Packed, &[1])),
+            Encoding::Packed
+        );
+        // Up to 512 segments is a plausible unpacked segment table.
+        assert_eq!(Encoding::detect(&[0xff, 1, 0, 0]), Encoding::Unpacked);
+        assert_eq!(Encoding::detect(&[0, 2, 0, 0]), Encoding::Packed);
+        // Too short to tell.
+        assert_eq!(Encoding::detect(&[0x10, 2]), Encoding::Unpacked);
+    }
+
+    #[test]
+    fn reads_consecutive_messages_in_either_encoding() {
+        for &encoding in &[Encoding::Unpacked, Encoding::Packed] {
+            let bytes = encode(encoding, &[1, 3, 2]);
+            let mut reader = FramesReader::new(&bytes[..], ReaderOptions::new(), None);
+            let lengths: Vec<u32> = reader
+                .by_ref()
+                .map(|message| message.unwrap().get().unwrap().get_frames().unwrap().len())
+                .collect();
+            assert_eq!(lengths, [1, 3, 2]);
+            assert_eq!(reader.encoding(), Some(encoding));
+        }
+    }
+
+    #[test]
+    fn reports_where_a_message_is_truncated() {
+        for &encoding in &[Encoding::Unpacked, Encoding::Packed] {
+            let bytes = encode(encoding, &[1, 1]);
+            let first = encode(encoding, &[1]).len();
+            let reader = FramesReader::new(&bytes[..bytes.len() - 1], ReaderOptions::new(), None);
+            match reader.last() {
+                Some(Err(ReadError::Truncated { index: 1, offset })) => {
+                    assert_eq!(offset, first as u64)
+                }
+                other => panic!(
+                    "expected a truncated second message, got {:?}",
+                    other.map(|r| r.err())
+                ),
+            }
+        }
+    }
+}
@@ -0,0 +1,367 @@
+//! Serves the frames files of a directory over RPC, and pulls them back.
+//!
+//! Clients pull batches of frames from a `FrameSource`, which only reads
+//! as much of its file as each batch needs.
+
+use crate::args;
+use crate::error::{self, Error as CliError};
+use crate::frames::{dump_frame, Counts};
+use crate::frames_capnp::frames;
+use crate::reader::{Encoding, FramesMessage, FramesReader, ReadError};
+use crate::recordings_capnp::{frame_source, recordings};
+use crate::rpc;
+use crate::subscribe::{self, SlowPolicy};
+use capnp::capability::Promise;
+use capnp::message::{Builder, ReaderOptions};
+use capnp::Error;
+use capnp_rpc::pry;
+use std::fmt::Display;
+use std::fs;
+use std::io::{self, Write};
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use tokio::runtime::current_thread;
+
+pub const DEFAULT_ADDR: &str = "127.0.0.1:9496";
+
+/// Sent as the default `maxBytes` of `pull`.
+const DEFAULT_MAX_BYTES: u64 = 1 << 20;
+
+fn failed(e: impl Display) -> Error {
+    Error::failed(e.to_string())
+}
+
+struct RecordingsImpl {
+    dir: PathBuf,
+    options: ReaderOptions,
+    /// How many pushes a subscriber can leave unanswered.
+    max_in_flight: usize,
+    slow: SlowPolicy,
+}
+
+impl RecordingsImpl {
+    /// The regular files of the directory, sorted. Index sidecars are left
+    /// out, as they are not frames files.
+    fn names(&self) -> io::Result<Vec<String>> {
+        let mut names = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let entry = entry?;
+            if !entry.file_type()?.is_file() {
+                continue;
+            }
+            if let Ok(name) = entry.file_name().into_string() {
+                if !name.ends_with(".idx") {
+                    names.push(name);
+                }
+            }
+        }
+        names.sort();
+        Ok(names)
+    }
+
+    fn open_recording(&self, name: &str) -> Result<FramesReader<fs::File>, Error> {
+        // Only what `list` returns can be opened, so that names cannot
+        // reach outside the directory.
+        if !self.names()?.iter().any(|known| known == name) {
+            return Err(failed(format!("no recording named {:?}", name)));
+        }
+        let path = self.dir.join(name);
+        let f = error::open(&path.to_string_lossy()).map_err(failed)?;
+        Ok(FramesReader::new(f, self.options, None))
+    }
+}
+
+impl recordings::Server for RecordingsImpl {
+    fn list(
+        &mut self,
+        _params: recordings::ListParams,
+        mut results: recordings::ListResults,
+    ) -> Promise<(), Error> {
+        let names = pry!(self.names());
+        let mut list = results.get().init_names(names.len() as u32);
+        for (i, name) in names.iter().enumerate() {
+            list.set(i as u32, name);
+        }
+        Promise::ok(())
+    }
+
+    fn open(
+        &mut self,
+        params: recordings::OpenParams,
+        mut results: recordings::OpenResults,
+    ) -> Promise<(), Error> {
+        let name = pry!(pry!(params.get()).get_name());
+        let source = FrameSourceImpl {
+            reader: pry!(self.open_recording(name)),
+            current: None,
+        };
+        results
+            .get()
+            .set_source(frame_source::ToClient::new(source).into_client::<capnp_rpc::Server>());
+        Promise::ok(())
+    }
+
+    fn subscribe(
+        &mut self,
+        params: recordings::SubscribeParams,
+        mut results: recordings::SubscribeResults,
+    ) -> Promise<(), Error> {
+        let params = pry!(params.get());
+        let name = pry!(params.get_name());
+        let reader = pry!(self.open_recording(name));
+        let stream_ids = pry!(params.get_stream_ids()).iter().collect();
+        println!("Subscribed to {}", name);
+        results.get().set_subscription(subscribe::subscribe(
+            reader,
+            stream_ids,
+            pry!(params.get_sink()),
+            self.max_in_flight,
+            self.slow,
+        ));
+        Promise::ok(())
+    }
+}
+
+struct FrameSourceImpl {
+    reader: FramesReader<fs::File>,
+    /// A message with frames left over from the last batch, and the
+    /// position of the first of them.
+    current: Option<(FramesMessage, u32)>,
+}
+
+impl FrameSourceImpl {
+    fn next_message(&mut self) -> Result<Option<(FramesMessage, u32)>, ReadError> {
+        match self.current.take() {
+            Some(current) => Ok(Some(current)),
+            None => Ok(self.reader.next().transpose()?.map(|message| (message, 0))),
+        }
+    }
+
+    fn fill(
+        &mut self,
+        max_bytes: u64,
+        mut results: frame_source::next_results::Builder,
+    ) -> Result<(), ReadError> {
+        // The messages the batch takes frames from, each with the range of
+        // positions it takes and its number of frames.
+        let mut batch = Vec::new();
+        let (mut count, mut bytes) = (0, 0);
+        let mut done = false;
+        loop {
+            let (message, start) = match self.next_message()? {
+                Some(next) => next,
+                None => {
+                    done = true;
+                    break;
+                }
+            };
+            let explain = |e| message.explain(e);
+            let frames = message
+                .get()
+                .and_then(|root| root.get_frames())
+                .map_err(explain)?;
+            let mut end = start;
+            while end < frames.len() {
+                let size = frames.get(end).total_size().map_err(explain)?.word_count * 8;
+                if count > 0 && bytes + size > max_bytes {
+                    break;
+                }
+                bytes += size;
+                count += 1;
+                end += 1;
+            }
+            let len = frames.len();
+            batch.push((message, start, end, len));
+            if end < len {
+                break;
+            }
+        }
+
+        let list = results.reborrow().init_frames(count);
+        let mut i = 0;
+        for (message, start, end, _) in &batch {
+            let explain = |e| message.explain(e);
+            let frames = message
+                .get()
+                .and_then(|root| root.get_frames())
+                .map_err(explain)?;
+            for position in *start..*end {
+                list.set_with_caveats(i, frames.get(position))
+                    .map_err(explain)?;
+                i += 1;
+            }
+        }
+        if let Some((message, _, end, len)) = batch.pop() {
+            if end < len {
+                self.current = Some((message, end));
+            }
+        }
+        results.set_done(done);
+        Ok(())
+    }
+}
+
+impl frame_source::Server for FrameSourceImpl {
+    fn next(
+        &mut self,
+        params: frame_source::NextParams,
+        mut results: frame_source::NextResults,
+    ) -> Promise<(), Error> {
+        let max_bytes = pry!(params.get()).get_max_bytes();
+        pry!(self.fill(max_bytes, results.get()).map_err(failed));
+        Promise::ok(())
+    }
+}
+
+pub fn serve_recordings_main(args: &[String]) -> Result<(), CliError> {
+    let mut addr: SocketAddr = DEFAULT_ADDR.parse().unwrap();
+    let mut dir = PathBuf::from(".");
+    let mut options = ReaderOptions::new();
+    let (mut max_in_flight, mut slow) = (8, SlowPolicy::Pause);
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--listen" => addr = args::value(arg, args.next())?,
+            "--max-in-flight" => max_in_flight = args::value(arg, args.next())?,
+            "--slow" => slow = args::value(arg, args.next())?,
+            "--traversal-limit" => {
+                options.traversal_limit_in_words(args::value(arg, args.next())?);
+            }
+            "--nesting-limit" => {
+                options.nesting_limit(args::value(arg, args.next())?);
+            }
+            flag if flag.starts_with("--") => return Err(args::unknown_option(flag)),
+            path => dir = PathBuf::from(path),
+        }
+    }
+    if max_in_flight == 0 {
+        return Err(CliError::Usage(
+            "--max-in-flight must be at least 1".to_string(),
+        ));
+    }
+    if !dir.is_dir() {
+        return Err(CliError::MissingFile(dir.to_string_lossy().into_owned()));
+    }
+
+    let mut runtime = current_thread::Runtime::new()?;
+    println!("Serving {}", dir.display());
+    let recordings = recordings::ToClient::new(RecordingsImpl {
+        dir,
+        options,
+        max_in_flight,
+        slow,
+    })
+    .into_client::<capnp_rpc::Server>();
+    rpc::serve(&mut runtime, &addr, recordings.client)
+}
+
+/// Connects to a `serve-recordings` server, leaving its RPC system running
+/// on `runtime`.
+pub fn connect(
+    runtime: &mut current_thread::Runtime,
+    addr: &SocketAddr,
+) -> Result<recordings::Client, CliError> {
+    println!("Connecting to {}", addr);
+    rpc::connect(runtime, addr)
+}
+
+pub fn pull_main(args: &[String]) -> Result<(), CliError> {
+    let mut addr: SocketAddr = DEFAULT_ADDR.parse().unwrap();
+    let (mut name, mut list) = (None, false);
+    let mut max_bytes = DEFAULT_MAX_BYTES;
+    let (mut output, mut encoding) = (None, Encoding::Unpacked);
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--connect" => addr = args::value(arg, args.next())?,
+            "--list" => list = true,
+            "--max-bytes" => max_bytes = args::value(arg, args.next())?,
+            "--output" => output = Some(args::value::<String>(arg, args.next())?),
+            "--packed" => encoding = Encoding::Packed,
+            flag if flag.starts_with("--") => return Err(args::unknown_option(flag)),
+            recording => name = Some(recording.to_string()),
+        }
+    }
+    let name = match (name, list) {
+        (Some(name), false) => name,
+        (None, true) => String::new(),
+        _ => {
+            return Err(CliError::Usage(
+                "pull needs either a recording name or --list".to_string(),
+            ))
+        }
+    };
+
+    let mut runtime = current_thread::Runtime::new()?;
+    let recordings = connect(&mut runtime, &addr)?;
+
+    if list {
+        let response = runtime
+            .block_on(recordings.list_request().send().promise)
+            .map_err(CliError::Rpc)?;
+        for name in response
+            .get()
+            .and_then(|r| r.get_names())
+            .map_err(CliError::Rpc)?
+            .iter()
+        {
+            println!("{}", name.map_err(CliError::Rpc)?);
+        }
+        return Ok(());
+    }
+
+    let mut request = recordings.open_request();
+    request.get().set_name(&name);
+    let source = request.send().pipeline.get_source();
+
+    let mut out = match &output {
+        Some(path) => Some(io::BufWriter::new(fs::File::create(path)?)),
+        None => None,
+    };
+    let (mut batches, mut frames, mut counts) = (0, 0, Counts::default());
+    loop {
+        let mut request = source.next_request();
+        request.get().set_max_bytes(max_bytes);
+        let response = runtime
+            .block_on(request.send().promise)
+            .map_err(CliError::Rpc)?;
+        let batch = response.get().map_err(CliError::Rpc)?;
+        let list = batch.get_frames().map_err(CliError::Rpc)?;
+
+        if list.len() > 0 {
+            match &mut out {
+                Some(f) => {
+                    let mut message = Builder::new_default();
+                    message.init_root::<frames::Builder>().set_frames(list)?;
+                    encoding.write_message(f, &message)?;
+                }
+                None => {
+                    println!("batch #{}: {} frames", batches, list.len());
+                    for frame in list.iter() {
+                        dump_frame(frame, &mut counts)?;
+                    }
+                }
+            }
+            batches += 1;
+            frames += list.len();
+        }
+        if batch.get_done() {
+            break;
+        }
+    }
+
+    match out {
+        Some(mut f) => {
+            f.flush()?;
+            println!(
+                "Wrote {} frames in {} messages to {} ({})",
+                frames,
+                batches,
+                output.unwrap_or_default(),
+                encoding.name()
+            );
+        }
+        None => counts.print(batches, "batches"),
+    }
+    Ok(())
+}
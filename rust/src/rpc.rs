@@ -0,0 +1,74 @@
+//! The two-party RPC setup shared by the servers and clients: one TCP
+//! connection per `RpcSystem`, with the server side offering a bootstrap
+//! capability and the client side asking for it.
+
+use crate::error::Error;
+use capnp::capability::{self, FromClientHook};
+use capnp_rpc::rpc_twoparty_capnp::Side;
+use capnp_rpc::{twoparty, RpcSystem};
+use futures::{Future, Stream};
+use std::io;
+use std::net::SocketAddr;
+use tokio::io::AsyncRead;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::runtime::current_thread;
+
+fn rpc_system(
+    stream: TcpStream,
+    side: Side,
+    bootstrap: Option<capability::Client>,
+) -> io::Result<RpcSystem<Side>> {
+    stream.set_nodelay(true)?;
+    let (reader, writer) = stream.split();
+    let network = twoparty::VatNetwork::new(
+        reader,
+        io::BufWriter::new(writer), // for performance (flush between messages)
+        side,
+        Default::default(),
+    );
+    Ok(RpcSystem::new(Box::new(network), bootstrap))
+}
+
+/// Serves `bootstrap` over an accepted connection. The returned future
+/// runs the connection, and logs how it failed, if it did.
+pub fn accept(
+    stream: TcpStream,
+    bootstrap: capability::Client,
+) -> io::Result<impl Future<Item = (), Error = ()>> {
+    let rpc_system = rpc_system(stream, Side::Server, Some(bootstrap))?;
+    Ok(rpc_system.map_err(|e| println!("Encountered error: {}", e)))
+}
+
+/// Serves `bootstrap` to every client that connects to `addr`, forever.
+pub fn serve(
+    runtime: &mut current_thread::Runtime,
+    addr: &SocketAddr,
+    bootstrap: capability::Client,
+) -> Result<(), Error> {
+    let listener = TcpListener::bind(addr)?;
+    println!("Listening on {}", addr);
+
+    let done = listener.incoming().for_each(move |stream| {
+        println!("Client joined");
+        let bootstrap = capability::Client::new(bootstrap.hook.add_ref());
+        current_thread::spawn(accept(stream, bootstrap)?);
+        Ok(())
+    });
+    runtime.block_on(done)?;
+    Ok(())
+}
+
+/// Connects to the server at `addr` and returns its bootstrap capability,
+/// leaving the connection running on `runtime`.
+pub fn connect<T: FromClientHook>(
+    runtime: &mut current_thread::Runtime,
+    addr: &SocketAddr,
+) -> Result<T, Error> {
+    let stream = runtime
+        .block_on(TcpStream::connect(addr))
+        .map_err(|e| Error::Rpc(e.into()))?;
+    let mut rpc_system = rpc_system(stream, Side::Client, None)?;
+    let bootstrap = rpc_system.bootstrap(Side::Server);
+    runtime.spawn(rpc_system.map_err(|e| println!("Encountered error: {}", e)));
+    Ok(bootstrap)
+}
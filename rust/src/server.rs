@@ -0,0 +1,206 @@
+use crate::calculator_capnp::calculator;
+use crate::error::Error as CliError;
+use crate::rpc;
+use capnp::capability::Promise;
+use capnp::primitive_list;
+use capnp::Error;
+use capnp_rpc::pry;
+use futures::{future, Future};
+use tokio::runtime::current_thread;
+
+struct ValueImpl {
+    value: f64,
+}
+
+impl calculator::value::Server for ValueImpl {
+    fn read(
+        &mut self,
+        _params: calculator::value::ReadParams,
+        mut results: calculator::value::ReadResults,
+    ) -> Promise<(), Error> {
+        results.get().set_value(self.value);
+        Promise::ok(())
+    }
+}
+
+/// Evaluates `expression`, resolving `parameter` references against `params`
+/// when called from a function body.
+fn evaluate_impl(
+    expression: calculator::expression::Reader,
+    params: Option<primitive_list::Reader<f64>>,
+) -> Promise<f64, Error> {
+    match pry!(expression.which()) {
+        calculator::expression::Literal(v) => Promise::ok(v),
+        calculator::expression::PreviousResult(p) => Promise::from_future(
+            pry!(p)
+                .read_request()
+                .send()
+                .promise
+                .and_then(|v| Ok(v.get()?.get_value())),
+        ),
+        calculator::expression::Parameter(p) => match params {
+            Some(params) if p < params.len() => Promise::ok(params.get(p)),
+            Some(params) => Promise::err(Error::failed(format!(
+                "parameter {} used, but only have {} parameters",
+                p,
+                params.len()
+            ))),
+            None => Promise::err(Error::failed(format!(
+                "parameter {} used outside of function context",
+                p
+            ))),
+        },
+        calculator::expression::Call(call) => {
+            let func = pry!(call.get_function());
+            let param_values = pry!(call.get_params())
+                .iter()
+                .map(|p| evaluate_impl(p, params))
+                .collect::<Vec<_>>();
+            Promise::from_future(
+                future::join_all(param_values).and_then(move |param_values| {
+                    let mut request = func.call_request();
+                    {
+                        let mut params = request.get().init_params(param_values.len() as u32);
+                        for (i, v) in param_values.iter().enumerate() {
+                            params.set(i as u32, *v);
+                        }
+                    }
+                    request
+                        .send()
+                        .promise
+                        .and_then(|result| Ok(result.get()?.get_value()))
+                }),
+            )
+        }
+    }
+}
+
+/// A function defined by the client through `defFunction()`.
+///
+/// The body is kept in an imbued message because it may hold capabilities
+/// (other functions) that it calls into.
+struct FunctionImpl {
+    param_count: u32,
+    body: capnp_rpc::ImbuedMessageBuilder<capnp::message::HeapAllocator>,
+}
+
+impl FunctionImpl {
+    fn new(param_count: u32, body: calculator::expression::Reader) -> capnp::Result<FunctionImpl> {
+        let mut result = FunctionImpl {
+            param_count,
+            body: capnp_rpc::ImbuedMessageBuilder::new(capnp::message::HeapAllocator::new()),
+        };
+        result.body.set_root(body)?;
+        Ok(result)
+    }
+}
+
+impl calculator::function::Server for FunctionImpl {
+    fn call(
+        &mut self,
+        params: calculator::function::CallParams,
+        mut results: calculator::function::CallResults,
+    ) -> Promise<(), Error> {
+        let params = pry!(pry!(params.get()).get_params());
+        if params.len() != self.param_count {
+            return Promise::err(Error::failed(format!(
+                "expected {} arguments, got {}",
+                self.param_count,
+                params.len()
+            )));
+        }
+
+        let body = pry!(self.body.get_root::<calculator::expression::Builder>());
+        Promise::from_future(
+            evaluate_impl(body.into_reader(), Some(params)).map(move |v| {
+                results.get().set_value(v);
+            }),
+        )
+    }
+}
+
+/// One of the built-in arithmetic operators handed out by `getOperator()`.
+struct OperatorImpl {
+    op: calculator::Operator,
+}
+
+impl calculator::function::Server for OperatorImpl {
+    fn call(
+        &mut self,
+        params: calculator::function::CallParams,
+        mut results: calculator::function::CallResults,
+    ) -> Promise<(), Error> {
+        let params = pry!(pry!(params.get()).get_params());
+        if params.len() != 2 {
+            return Promise::err(Error::failed(format!(
+                "expected 2 arguments, got {}",
+                params.len()
+            )));
+        }
+
+        let (a, b) = (params.get(0), params.get(1));
+        let value = match self.op {
+            calculator::Operator::Add => a + b,
+            calculator::Operator::Subtract => a - b,
+            calculator::Operator::Multiply => a * b,
+            calculator::Operator::Divide => a / b,
+        };
+        results.get().set_value(value);
+        Promise::ok(())
+    }
+}
+
+struct CalculatorImpl;
+
+impl calculator::Server for CalculatorImpl {
+    fn evaluate(
+        &mut self,
+        params: calculator::EvaluateParams,
+        mut results: calculator::EvaluateResults,
+    ) -> Promise<(), Error> {
+        let expression = pry!(pry!(params.get()).get_expression());
+        Promise::from_future(evaluate_impl(expression, None).map(move |value| {
+            results.get().set_value(
+                calculator::value::ToClient::new(ValueImpl { value })
+                    .into_client::<capnp_rpc::Server>(),
+            );
+        }))
+    }
+
+    fn def_function(
+        &mut self,
+        params: calculator::DefFunctionParams,
+        mut results: calculator::DefFunctionResults,
+    ) -> Promise<(), Error> {
+        let params = pry!(params.get());
+        let func = pry!(FunctionImpl::new(
+            params.get_param_count() as u32,
+            pry!(params.get_body())
+        ));
+        results
+            .get()
+            .set_func(calculator::function::ToClient::new(func).into_client::<capnp_rpc::Server>());
+        Promise::ok(())
+    }
+
+    fn get_operator(
+        &mut self,
+        params: calculator::GetOperatorParams,
+        mut results: calculator::GetOperatorResults,
+    ) -> Promise<(), Error> {
+        let op = pry!(pry!(params.get()).get_op());
+        results.get().set_func(
+            calculator::function::ToClient::new(OperatorImpl { op })
+                .into_client::<capnp_rpc::Server>(),
+        );
+        Promise::ok(())
+    }
+}
+
+pub fn server_main() -> Result<(), CliError> {
+    let mut runtime = current_thread::Runtime::new()?;
+
+    let addr: std::net::SocketAddr = ([127, 0, 0, 1], 9494).into();
+    let calc = calculator::ToClient::new(CalculatorImpl).into_client::<capnp_rpc::Server>();
+    rpc::serve(&mut runtime, &addr, calc.client)
+}
@@ -0,0 +1,216 @@
+use crate::args::{self, InputOptions};
+use crate::error::Error;
+use crate::frames::pixel_format_name;
+use crate::frames_capnp::{frame, rational};
+use crate::rational::Rational;
+use crate::reader::FramesMessage;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::convert::TryFrom;
+use std::io;
+
+#[derive(Default)]
+struct Totals {
+    audio: usize,
+    video: usize,
+    bytes: u64,
+    /// Frames whose pts has a zero denominator.
+    invalid_pts: usize,
+    first_pts: Option<Rational>,
+    last_pts: Option<Rational>,
+    resolutions: BTreeSet<(u32, u32)>,
+    pixel_formats: BTreeSet<String>,
+}
+
+impl Totals {
+    fn add(&mut self, pts: rational::Reader, bytes: usize) {
+        self.bytes += bytes as u64;
+        match Rational::try_from(pts) {
+            Ok(pts) => {
+                self.first_pts = Some(self.first_pts.map_or(pts, |first| first.min(pts)));
+                self.last_pts = Some(self.last_pts.map_or(pts, |last| last.max(pts)));
+            }
+            Err(_) => self.invalid_pts += 1,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct StreamStats {
+    stream: u32,
+    /// `audio`, `video`, or `mixed` for a stream id used by both.
+    kind: &'static str,
+    frames: usize,
+    payload_bytes: u64,
+    invalid_pts: usize,
+    first_pts: Option<Rational>,
+    last_pts: Option<Rational>,
+    /// From the first pts to the last, so not counting the last frame.
+    duration: Option<Rational>,
+    average_interval: Option<Rational>,
+    /// Payload bits per second over `duration`, rounded.
+    bitrate: Option<u64>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    resolutions: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pixel_formats: Vec<String>,
+}
+
+impl StreamStats {
+    fn new(stream: u32, totals: Totals) -> StreamStats {
+        let frames = totals.audio + totals.video;
+        let kind = match (totals.audio, totals.video) {
+            (_, 0) => "audio",
+            (0, _) => "video",
+            _ => "mixed",
+        };
+        let duration = totals
+            .first_pts
+            .zip(totals.last_pts)
+            .and_then(|(first, last)| last.checked_sub(first));
+        let timed_frames = (frames - totals.invalid_pts) as i64;
+        let average_interval = duration
+            .filter(|_| timed_frames > 1)
+            .and_then(|d| Rational::new(d.num(), d.den().checked_mul(timed_frames - 1)?).ok());
+        let bitrate = duration
+            .filter(|d| d.num() > 0)
+            .map(|d| (totals.bytes as f64 * 8.0 / d.to_f64()).round() as u64);
+        StreamStats {
+            stream,
+            kind,
+            frames,
+            payload_bytes: totals.bytes,
+            invalid_pts: totals.invalid_pts,
+            first_pts: totals.first_pts,
+            last_pts: totals.last_pts,
+            duration,
+            average_interval,
+            bitrate,
+            resolutions: totals
+                .resolutions
+                .iter()
+                .map(|(w, h)| format!("{}x{}", w, h))
+                .collect(),
+            pixel_formats: totals.pixel_formats.into_iter().collect(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Report {
+    frames: usize,
+    /// Frames of kinds this build does not know, which have no stream id.
+    unknown: usize,
+    streams: Vec<StreamStats>,
+}
+
+fn add_message(
+    message: &FramesMessage,
+    streams: &mut BTreeMap<u32, Totals>,
+    unknown: &mut usize,
+) -> capnp::Result<()> {
+    for frame in message.get()?.get_frames()?.iter() {
+        match frame.which() {
+            Ok(frame::AudioFrame(af)) => {
+                let af = af?;
+                let totals = streams.entry(af.get_stream_id()).or_default();
+                totals.audio += 1;
+                totals.add(af.get_pts()?, af.get_data()?.len());
+            }
+            Ok(frame::VideoFrame(vf)) => {
+                let vf = vf?;
+                let totals = streams.entry(vf.get_stream_id()).or_default();
+                totals.video += 1;
+                totals.add(vf.get_pts()?, vf.get_data()?.len());
+                totals.resolutions.insert((vf.get_width(), vf.get_height()));
+                totals
+                    .pixel_formats
+                    .insert(pixel_format_name(vf.get_pixel_format()));
+            }
+            Err(capnp::NotInSchema(_)) => *unknown += 1,
+        }
+    }
+    Ok(())
+}
+
+fn seconds(pts: Rational) -> String {
+    match pts.to_duration() {
+        Ok(duration) => format!("{:?}", duration),
+        Err(_) => format!("{}s", pts.to_f64()),
+    }
+}
+
+fn print_text(report: &Report) {
+    for s in &report.streams {
+        println!(
+            "stream {} ({}): {} frames, {} payload bytes",
+            s.stream, s.kind, s.frames, s.payload_bytes
+        );
+        if let (Some(first), Some(last)) = (s.first_pts, s.last_pts) {
+            println!("  pts: {} to {}", first, last);
+        }
+        if let Some(duration) = s.duration {
+            println!("  duration: {} ({})", duration, seconds(duration));
+        }
+        if let Some(interval) = s.average_interval {
+            println!("  average interval: {} ({})", interval, seconds(interval));
+        }
+        if let Some(bitrate) = s.bitrate {
+            println!("  bitrate: {} bit/s", bitrate);
+        }
+        if s.invalid_pts > 0 {
+            println!("  frames with an invalid pts: {}", s.invalid_pts);
+        }
+        if !s.resolutions.is_empty() {
+            println!("  resolutions: {}", s.resolutions.join(", "));
+        }
+        if !s.pixel_formats.is_empty() {
+            println!("  pixel formats: {}", s.pixel_formats.join(", "));
+        }
+    }
+    println!(
+        "{} frames in {} streams ({} unknown)",
+        report.frames,
+        report.streams.len(),
+        report.unknown
+    );
+}
+
+pub fn stats_main(args: &[String]) -> Result<(), Error> {
+    let mut input = InputOptions::default();
+    let mut json = false;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => json = true,
+            _ if input.parse_arg(arg, &mut args)? => {}
+            flag => return Err(args::unknown_option(flag)),
+        }
+    }
+
+    let (mut streams, mut unknown) = (BTreeMap::new(), 0);
+    for message in input.open()? {
+        let message = message?;
+        add_message(&message, &mut streams, &mut unknown).map_err(|e| message.explain(e))?;
+    }
+    let streams: Vec<_> = streams
+        .into_iter()
+        .map(|(stream, totals)| StreamStats::new(stream, totals))
+        .collect();
+    let report = Report {
+        frames: streams.iter().map(|s| s.frames).sum::<usize>() + unknown,
+        unknown,
+        streams,
+    };
+    if report.frames == 0 {
+        return Err(Error::NoFrames(input.path));
+    }
+
+    if json {
+        let json = serde_json::to_string_pretty(&report).map_err(io::Error::from)?;
+        println!("{}", json);
+    } else {
+        print_text(&report);
+    }
+    Ok(())
+}
@@ -0,0 +1,179 @@
+//! Sends frames files over TCP, one `Frames` message after the other in
+//! the unpacked stream framing: each message is preceded by its segment
+//! table, which says how long it is. The receiver decodes messages as they
+//! arrive rather than waiting for the sender to finish.
+
+use crate::args::{self, InputOptions};
+use crate::error::Error;
+use crate::frames::{dump_frames, Counts};
+use crate::reader::{Encoding, FramesMessage, ReadError};
+use capnp::message::{self, ReaderOptions, ReaderSegments};
+use capnp::OutputSegments;
+use capnp_futures::serialize::AsOutputSegments;
+use capnp_futures::ReadStream;
+use futures::Stream;
+use std::fs;
+use std::io::{self, Write};
+use std::net::SocketAddr;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::runtime::current_thread;
+
+const DEFAULT_ADDR: &str = "127.0.0.1:9495";
+
+/// The segments of a message that was read, to be sent on as they are.
+struct Segments<S>(S);
+
+impl<S: ReaderSegments> AsOutputSegments for Segments<S> {
+    fn as_output_segments(&self) -> OutputSegments<'_> {
+        OutputSegments::MultiSegment((0..).map_while(|id| self.0.get_segment(id)).collect())
+    }
+}
+
+/// The size of a message in the stream framing, as the number of words in
+/// its segments and its total length in bytes, segment table included.
+fn framed_size<S: ReaderSegments>(segments: &S) -> (u64, u64) {
+    let (mut count, mut words) = (0, 0);
+    while let Some(segment) = segments.get_segment(count) {
+        words += segment.len() as u64;
+        count += 1;
+    }
+    let table = (4 + 4 * u64::from(count)).div_ceil(8) * 8;
+    (words, table + words * 8)
+}
+
+pub fn stream_send_main(args: &[String]) -> Result<(), Error> {
+    let mut input = InputOptions::default();
+    let mut addr: SocketAddr = DEFAULT_ADDR.parse().unwrap();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--connect" => addr = args::value(arg, args.next())?,
+            _ if input.parse_arg(arg, &mut args)? => {}
+            flag => return Err(args::unknown_option(flag)),
+        }
+    }
+
+    let mut reader = input.open()?;
+    let mut runtime = current_thread::Runtime::new()?;
+    println!("Connecting to {}", addr);
+    let mut stream = runtime.block_on(TcpStream::connect(&addr))?;
+    stream.set_nodelay(true)?;
+
+    let (mut messages, mut frames) = (0, 0);
+    for message in reader.by_ref() {
+        let message = message?;
+        frames += message
+            .get()
+            .and_then(|root| root.get_frames())
+            .map_err(|e| message.explain(e))?
+            .len();
+        let write =
+            capnp_futures::serialize::write_message(stream, Segments(message.into_segments()));
+        stream = runtime.block_on(write).map_err(Error::Stream)?.0;
+        messages += 1;
+    }
+    runtime.block_on(tokio::io::shutdown(stream))?;
+
+    println!(
+        "Sent {} frames in {} messages to {}",
+        frames, messages, addr
+    );
+    Ok(())
+}
+
+/// Where `stream-recv` puts what it receives.
+enum Sink {
+    Dump(Counts),
+    File(io::BufWriter<fs::File>, Encoding),
+}
+
+impl Sink {
+    fn receive<S: ReaderSegments>(&mut self, message: FramesMessage<S>) -> Result<(), Error> {
+        match self {
+            Sink::Dump(counts) => dump_frames(&message, counts).map_err(|e| message.explain(e))?,
+            Sink::File(f, encoding) => {
+                // Only keep what is a frames message.
+                message
+                    .get()
+                    .and_then(|root| root.get_frames())
+                    .map_err(|e| message.explain(e))?;
+                encoding.write_segments(f, &message.into_segments())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn stream_recv_main(args: &[String]) -> Result<(), Error> {
+    let mut addr: SocketAddr = DEFAULT_ADDR.parse().unwrap();
+    let (mut output, mut encoding) = (None, Encoding::Unpacked);
+    let mut options = ReaderOptions::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--listen" => addr = args::value(arg, args.next())?,
+            "--output" => output = Some(args::value::<String>(arg, args.next())?),
+            "--packed" => encoding = Encoding::Packed,
+            "--traversal-limit" => {
+                options.traversal_limit_in_words(args::value(arg, args.next())?);
+            }
+            "--nesting-limit" => {
+                options.nesting_limit(args::value(arg, args.next())?);
+            }
+            flag => return Err(args::unknown_option(flag)),
+        }
+    }
+
+    let mut sink = match &output {
+        Some(path) => Sink::File(io::BufWriter::new(fs::File::create(path)?), encoding),
+        None => Sink::Dump(Counts::default()),
+    };
+
+    let mut runtime = current_thread::Runtime::new()?;
+    let listener = TcpListener::bind(&addr)?;
+    println!("Listening on {}", addr);
+    let (stream, _) = runtime
+        .block_on(listener.incoming().into_future())
+        .map_err(|(e, _)| e)?;
+    let stream = stream.expect("incoming connections never end");
+    println!("Sender connected from {}", stream.peer_addr()?);
+
+    let (mut index, mut offset) = (0, 0);
+    let mut messages = ReadStream::new(stream, options);
+    loop {
+        let message = match runtime.block_on(messages.into_future()) {
+            Ok((Some(message), rest)) => {
+                messages = rest;
+                message
+            }
+            Ok((None, _)) => break,
+            // capnp only tells a connection closed mid-message apart by
+            // its description.
+            Err((e, _)) if e.description == "Premature EOF" => {
+                return Err(ReadError::Truncated { index, offset }.into())
+            }
+            Err((e, _)) => return Err(Error::Stream(e)),
+        };
+
+        let segments = message.into_segments();
+        let (words, len) = framed_size(&segments);
+        let message = message::Reader::new(segments, options);
+        sink.receive(FramesMessage::new(message, index, offset, words, options))?;
+        index += 1;
+        offset += len;
+    }
+
+    match sink {
+        Sink::Dump(counts) => counts.print(index, "messages"),
+        Sink::File(mut f, encoding) => {
+            f.flush()?;
+            println!(
+                "Wrote {} messages to {} ({})",
+                index,
+                output.unwrap_or_default(),
+                encoding.name()
+            );
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,541 @@
+//! Pushing the frames of a recording to subscribers, and subscribing.
+//!
+//! Each subscription reads its recording on its own and pushes frames as
+//! fast as its sink answers, with at most `max_in_flight` pushes waiting
+//! for an answer. A `SlowPolicy` says what to do with a sink that falls
+//! that far behind: wait for it, or cut it off if it stays there for
+//! `STALL_LIMIT`.
+
+use crate::args;
+use crate::demux::stream_and_pts;
+use crate::error::Error as CliError;
+use crate::frames::{dump_frame, Counts};
+use crate::frames_capnp::{frame, frames};
+use crate::reader::{Encoding, FramesMessage, FramesReader};
+use crate::recordings;
+use crate::recordings_capnp::{frame_sink, subscription};
+use crate::rpc;
+use capnp::capability::Promise;
+use capnp::message::{Builder, HeapAllocator};
+use capnp::Error;
+use capnp_rpc::pry;
+use futures::stream::FuturesUnordered;
+use futures::sync::oneshot;
+use futures::{Async, Future, Poll, Stream};
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::fs;
+use std::io::{self, Write};
+use std::net::SocketAddr;
+use std::rc::Rc;
+use std::slice;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+use tokio::runtime::current_thread;
+use tokio::timer::Delay;
+
+pub const DEFAULT_SINK_ADDR: &str = "127.0.0.1:9497";
+
+/// How long a sink may leave `max_in_flight` pushes unanswered before
+/// `SlowPolicy::Drop` cuts it off.
+const STALL_LIMIT: Duration = Duration::from_secs(1);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SlowPolicy {
+    /// Stop reading until the sink answers a push.
+    Pause,
+    /// The same, but for at most `STALL_LIMIT`: then stop pushing to the
+    /// sink for good, and count the frames it misses.
+    Drop,
+}
+
+impl FromStr for SlowPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<SlowPolicy, String> {
+        match s {
+            "pause" => Ok(SlowPolicy::Pause),
+            "drop" => Ok(SlowPolicy::Drop),
+            _ => Err("expected pause or drop".to_string()),
+        }
+    }
+}
+
+/// Pushes frames to a sink, keeping track of the pushes it has not
+/// answered yet.
+pub struct Pusher {
+    sink: frame_sink::Client,
+    max_in_flight: usize,
+    policy: SlowPolicy,
+    in_flight: FuturesUnordered<Promise<(), Error>>,
+    pushed: u64,
+    dropped: u64,
+    /// Whether the sink fell behind under `SlowPolicy::Drop`.
+    cut_off: bool,
+    /// Since when `max_in_flight` pushes have been unanswered, under
+    /// `SlowPolicy::Drop`: fires at the end of the `STALL_LIMIT`.
+    stalled: Option<Delay>,
+}
+
+impl Pusher {
+    pub fn new(sink: frame_sink::Client, max_in_flight: usize, policy: SlowPolicy) -> Pusher {
+        Pusher {
+            sink,
+            max_in_flight,
+            policy,
+            in_flight: FuturesUnordered::new(),
+            pushed: 0,
+            dropped: 0,
+            cut_off: false,
+            stalled: None,
+        }
+    }
+
+    pub fn sink(&self) -> &frame_sink::Client {
+        &self.sink
+    }
+
+    pub fn pushed(&self) -> u64 {
+        self.pushed
+    }
+
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+
+    pub fn cut_off(&self) -> bool {
+        self.cut_off
+    }
+
+    /// Pushes `frame`, or drops it once the sink is cut off. Returns false
+    /// if `max_in_flight` pushes are unanswered, so that it can be offered
+    /// again once `poll_ready` says so.
+    pub fn offer(&mut self, frame: frame::Reader) -> capnp::Result<bool> {
+        if self.cut_off {
+            self.dropped += 1;
+            return Ok(true);
+        }
+        if self.in_flight.len() >= self.max_in_flight {
+            return Ok(false);
+        }
+        let mut request = self.sink.push_request();
+        request.get().set_frame(frame)?;
+        self.in_flight
+            .push(Promise::from_future(request.send().promise.map(|_| ())));
+        self.pushed += 1;
+        Ok(true)
+    }
+
+    /// Collects answers, and is ready once every push is answered.
+    pub fn poll_answers(&mut self) -> Poll<(), Error> {
+        while let Some(()) = futures::try_ready!(self.in_flight.poll()) {}
+        Ok(Async::Ready(()))
+    }
+
+    /// Collects answers, and is ready once `offer` would take a frame:
+    /// when a push is answered, or when the sink is cut off.
+    pub fn poll_ready(&mut self) -> Poll<(), Error> {
+        while let Async::Ready(Some(())) = self.in_flight.poll()? {}
+        if self.cut_off || self.in_flight.len() < self.max_in_flight {
+            self.stalled = None;
+            return Ok(Async::Ready(()));
+        }
+        if self.policy == SlowPolicy::Drop {
+            let stalled = self
+                .stalled
+                .get_or_insert_with(|| Delay::new(Instant::now() + STALL_LIMIT));
+            if stalled
+                .poll()
+                .map_err(|e| Error::failed(e.to_string()))?
+                .is_ready()
+            {
+                self.cut_off = true;
+                self.stalled = None;
+                return Ok(Async::Ready(()));
+            }
+        }
+        Ok(Async::NotReady)
+    }
+}
+
+/// One subscription: reads its recording and offers the wanted frames to
+/// a `Pusher`. Resolves to the number of frames pushed and dropped, or to
+/// `None` if the subscription was released first.
+struct Feed {
+    reader: FramesReader<fs::File>,
+    /// The wanted frames of the last message read, not offered yet. They
+    /// are copied out as soon as the message is read: going back to it
+    /// for every frame would soon run into its traversal limit.
+    queue: VecDeque<Builder<HeapAllocator>>,
+    stream_ids: Vec<u32>,
+    pusher: Pusher,
+    released: Rc<Cell<bool>>,
+}
+
+impl Feed {
+    fn wanted(&self, frame: frame::Reader) -> capnp::Result<bool> {
+        if self.stream_ids.is_empty() {
+            return Ok(true);
+        }
+        Ok(stream_and_pts(frame)?.is_some_and(|(stream, _)| self.stream_ids.contains(&stream)))
+    }
+
+    fn queue_frames(&mut self, message: &FramesMessage) -> capnp::Result<()> {
+        for frame in message.get()?.get_frames()?.iter() {
+            if self.wanted(frame)? {
+                let mut copy = Builder::new_default();
+                copy.set_root(frame)?;
+                self.queue.push_back(copy);
+            }
+        }
+        Ok(())
+    }
+
+    /// Offers the next frame, and returns whether it is done with it, or
+    /// `None` at the end of the recording.
+    fn offer_next(&mut self) -> capnp::Result<Option<bool>> {
+        let frame = match self.queue.front() {
+            Some(frame) => frame.get_root_as_reader()?,
+            None => match self.reader.next() {
+                Some(message) => {
+                    let message = message.map_err(|e| Error::failed(e.to_string()))?;
+                    self.queue_frames(&message)
+                        .map_err(|e| Error::failed(message.explain(e).to_string()))?;
+                    return Ok(Some(true));
+                }
+                None => return Ok(None),
+            },
+        };
+        let offered = self.pusher.offer(frame)?;
+        if offered {
+            self.queue.pop_front();
+        }
+        Ok(Some(offered))
+    }
+}
+
+impl Future for Feed {
+    type Item = Option<(u64, u64)>;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Option<(u64, u64)>, Error> {
+        loop {
+            if self.released.get() {
+                return Ok(Async::Ready(None));
+            }
+            let answered = self.pusher.poll_answers()?.is_ready();
+            // The unanswered pushes, or the end of a stall, will wake us up.
+            if !self.pusher.poll_ready()?.is_ready() {
+                return Ok(Async::NotReady);
+            }
+            match self.offer_next()? {
+                // Once cut off, this reads on to count the frames dropped.
+                Some(_) => {}
+                None if answered => {
+                    return Ok(Async::Ready(Some((
+                        self.pusher.pushed,
+                        self.pusher.dropped,
+                    ))))
+                }
+                None => return Ok(Async::NotReady),
+            }
+        }
+    }
+}
+
+/// Stops its feed once the subscriber releases it.
+struct SubscriptionImpl {
+    released: Rc<Cell<bool>>,
+}
+
+impl subscription::Server for SubscriptionImpl {}
+
+impl Drop for SubscriptionImpl {
+    fn drop(&mut self) {
+        self.released.set(true);
+    }
+}
+
+/// Starts pushing the frames of `reader` whose stream is in `stream_ids`
+/// (all of them, if it is empty) to `sink`.
+pub fn subscribe(
+    reader: FramesReader<fs::File>,
+    stream_ids: Vec<u32>,
+    sink: frame_sink::Client,
+    max_in_flight: usize,
+    policy: SlowPolicy,
+) -> subscription::Client {
+    let released = Rc::new(Cell::new(false));
+    let feed = Feed {
+        reader,
+        queue: VecDeque::new(),
+        stream_ids,
+        pusher: Pusher::new(sink.clone(), max_in_flight, policy),
+        released: released.clone(),
+    };
+    let done = feed.and_then(move |dropped| match dropped {
+        Some((pushed, dropped)) => {
+            if dropped == 0 {
+                println!("Subscription done: {} frames pushed", pushed);
+            } else {
+                println!(
+                    "Subscription cut off as too slow: {} frames pushed, {} dropped",
+                    pushed, dropped
+                );
+            }
+            let mut request = sink.done_request();
+            request.get().set_dropped(dropped);
+            Promise::from_future(request.send().promise.map(|_| ()))
+        }
+        None => {
+            println!("Subscription released");
+            Promise::ok(())
+        }
+    });
+    current_thread::spawn(done.map_err(|e| println!("Subscription failed: {}", e)));
+
+    subscription::ToClient::new(SubscriptionImpl { released }).into_client::<capnp_rpc::Server>()
+}
+
+/// What a `subscribe` or `sink` sink does with the frames pushed to it.
+enum Received {
+    Dump(Counts),
+    File(io::BufWriter<fs::File>, Encoding),
+}
+
+/// The sink `subscribe` hands to the server, and `sink` serves.
+struct FrameSinkImpl {
+    received: Rc<RefCell<Received>>,
+    frames: Rc<Cell<u64>>,
+    /// How long to take to answer each push, to play a slow subscriber.
+    delay: Duration,
+    done: Option<oneshot::Sender<u64>>,
+}
+
+impl FrameSinkImpl {
+    fn receive(&mut self, frame: frame::Reader) -> Result<(), CliError> {
+        match &mut *self.received.borrow_mut() {
+            Received::Dump(counts) => dump_frame(frame, counts)?,
+            Received::File(f, encoding) => {
+                let mut message = Builder::new_default();
+                {
+                    let list = message.init_root::<frames::Builder>().init_frames(1);
+                    list.set_with_caveats(0, frame)?;
+                }
+                encoding.write_message(f, &message)?;
+            }
+        }
+        self.frames.set(self.frames.get() + 1);
+        Ok(())
+    }
+}
+
+impl frame_sink::Server for FrameSinkImpl {
+    fn push(
+        &mut self,
+        params: frame_sink::PushParams,
+        _results: frame_sink::PushResults,
+    ) -> Promise<(), Error> {
+        let frame = pry!(pry!(params.get()).get_frame());
+        pry!(self
+            .receive(frame)
+            .map_err(|e| Error::failed(e.to_string())));
+        if self.delay == Duration::from_secs(0) {
+            return Promise::ok(());
+        }
+        Promise::from_future(
+            Delay::new(Instant::now() + self.delay).map_err(|e| Error::failed(e.to_string())),
+        )
+    }
+
+    fn done(
+        &mut self,
+        params: frame_sink::DoneParams,
+        _results: frame_sink::DoneResults,
+    ) -> Promise<(), Error> {
+        let dropped = pry!(params.get()).get_dropped();
+        if let Some(done) = self.done.take() {
+            let _ = done.send(dropped);
+        }
+        Promise::ok(())
+    }
+}
+
+/// The receiving end of a `FrameSinkImpl`.
+struct Receiving {
+    received: Rc<RefCell<Received>>,
+    frames: Rc<Cell<u64>>,
+    output: Option<String>,
+    done: oneshot::Receiver<u64>,
+}
+
+/// Options shared by the subcommands that receive pushed frames.
+struct SinkOptions {
+    output: Option<String>,
+    encoding: Encoding,
+    delay: Duration,
+}
+
+impl Default for SinkOptions {
+    fn default() -> Self {
+        SinkOptions {
+            output: None,
+            encoding: Encoding::Unpacked,
+            delay: Duration::from_secs(0),
+        }
+    }
+}
+
+impl SinkOptions {
+    /// Takes `arg` (and its value from `args`) if it is a sink option, and
+    /// returns whether it did.
+    fn parse_arg(&mut self, arg: &str, args: &mut slice::Iter<String>) -> Result<bool, CliError> {
+        match arg {
+            "--output" => self.output = Some(args::value(arg, args.next())?),
+            "--packed" => self.encoding = Encoding::Packed,
+            "--delay-ms" => self.delay = Duration::from_millis(args::value(arg, args.next())?),
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn sink(self) -> Result<(frame_sink::Client, Receiving), CliError> {
+        let received = Rc::new(RefCell::new(match &self.output {
+            Some(path) => {
+                Received::File(io::BufWriter::new(fs::File::create(path)?), self.encoding)
+            }
+            None => Received::Dump(Counts::default()),
+        }));
+        let frames = Rc::new(Cell::new(0));
+        let (done, receiver) = oneshot::channel();
+        let sink = FrameSinkImpl {
+            received: received.clone(),
+            frames: frames.clone(),
+            delay: self.delay,
+            done: Some(done),
+        };
+        let receiving = Receiving {
+            received,
+            frames,
+            output: self.output,
+            done: receiver,
+        };
+        Ok((
+            frame_sink::ToClient::new(sink).into_client::<capnp_rpc::Server>(),
+            receiving,
+        ))
+    }
+}
+
+impl Receiving {
+    /// Waits for the `peer` to call `done`, failing with `early` if the sink
+    /// is released before that, then reports what was received over `unit`.
+    fn finish(
+        self,
+        runtime: &mut current_thread::Runtime,
+        peer: &str,
+        unit: &str,
+        early: &str,
+    ) -> Result<(), CliError> {
+        let dropped = runtime
+            .block_on(self.done)
+            .map_err(|_| CliError::Rpc(Error::failed(early.to_string())))?;
+
+        match &mut *self.received.borrow_mut() {
+            Received::Dump(counts) => counts.print(1, unit),
+            Received::File(f, encoding) => {
+                f.flush()?;
+                println!(
+                    "Wrote {} frames to {} ({})",
+                    self.frames.get(),
+                    self.output.unwrap_or_default(),
+                    encoding.name()
+                );
+            }
+        }
+        println!("{} frames dropped by the {}", dropped, peer);
+        Ok(())
+    }
+}
+
+fn parse_stream_ids(flag: &str, value: Option<&String>) -> Result<Vec<u32>, CliError> {
+    let value = value.ok_or_else(|| CliError::Usage(format!("{} expects a value", flag)))?;
+    value
+        .split(',')
+        .map(|id| args::value(flag, Some(&id.to_string())))
+        .collect()
+}
+
+pub fn subscribe_main(args: &[String]) -> Result<(), CliError> {
+    let mut addr: SocketAddr = recordings::DEFAULT_ADDR.parse().unwrap();
+    let (mut name, mut stream_ids) = (None, Vec::new());
+    let mut options = SinkOptions::default();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--connect" => addr = args::value(arg, args.next())?,
+            "--streams" => stream_ids = parse_stream_ids(arg, args.next())?,
+            _ if options.parse_arg(arg, &mut args)? => {}
+            flag if flag.starts_with("--") => return Err(args::unknown_option(flag)),
+            recording => name = Some(recording.to_string()),
+        }
+    }
+    let name = name.ok_or_else(|| CliError::Usage("subscribe needs a recording".to_string()))?;
+
+    let (sink, receiving) = options.sink()?;
+    let mut runtime = current_thread::Runtime::new()?;
+    let recordings = recordings::connect(&mut runtime, &addr)?;
+    let mut request = recordings.subscribe_request();
+    {
+        let mut params = request.get();
+        params.set_name(&name);
+        let mut list = params.reborrow().init_stream_ids(stream_ids.len() as u32);
+        for (i, id) in stream_ids.iter().enumerate() {
+            list.set(i as u32, *id);
+        }
+        params.set_sink(sink);
+    }
+    // Holding on to the subscription keeps the pushes coming.
+    let _subscription = runtime
+        .block_on(request.send().promise)
+        .map_err(CliError::Rpc)?;
+    receiving.finish(
+        &mut runtime,
+        "server",
+        "subscription",
+        "the server ended the subscription early",
+    )
+}
+
+/// Serves a `FrameSink` to the first peer that connects, for `play` to
+/// push to, and returns once it is done.
+pub fn sink_main(args: &[String]) -> Result<(), CliError> {
+    let mut addr: SocketAddr = DEFAULT_SINK_ADDR.parse().unwrap();
+    let mut options = SinkOptions::default();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--listen" => addr = args::value(arg, args.next())?,
+            _ if options.parse_arg(arg, &mut args)? => {}
+            flag => return Err(args::unknown_option(flag)),
+        }
+    }
+    let (sink, receiving) = options.sink()?;
+
+    let mut runtime = current_thread::Runtime::new()?;
+    let listener = tokio::net::TcpListener::bind(&addr)?;
+    println!("Listening on {}", addr);
+    let (stream, _) = runtime
+        .block_on(listener.incoming().into_future())
+        .map_err(|(e, _)| e)?;
+    let stream = stream.expect("incoming connections never end");
+    println!("Sender connected from {}", stream.peer_addr()?);
+    runtime.spawn(rpc::accept(stream, sink.client)?);
+
+    receiving.finish(
+        &mut runtime,
+        "sender",
+        "connection",
+        "the sender went away before it was done",
+    )
+}
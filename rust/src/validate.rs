@@ -0,0 +1,221 @@
+use crate::args::{self, InputOptions};
+use crate::error::Error;
+use crate::frames_capnp::{audio_frame, frame, rational, video_frame};
+use crate::rational::Rational;
+use crate::reader::FramesMessage;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::io;
+
+/// Where a violation was found.
+#[derive(Clone, Copy, Serialize)]
+struct Location {
+    message: usize,
+    frame: usize,
+    stream: Option<u32>,
+}
+
+#[derive(Serialize)]
+struct Violation {
+    #[serde(flatten)]
+    at: Location,
+    kind: &'static str,
+    detail: String,
+}
+
+#[derive(Serialize)]
+struct Report {
+    frames: usize,
+    violations: Vec<Violation>,
+}
+
+struct Validator {
+    /// Bytes per audio sample, which frames files do not record.
+    sample_size: u64,
+    last_pts: HashMap<u32, Rational>,
+    report: Report,
+}
+
+impl Validator {
+    fn violation(&mut self, at: Location, kind: &'static str, detail: String) {
+        self.report.violations.push(Violation { at, kind, detail });
+    }
+
+    fn check_pts(&mut self, at: Location, has_pts: bool, pts: rational::Reader) {
+        let stream = at.stream.unwrap_or_default();
+        if !has_pts {
+            self.violation(at, "missing-pts", "frame has no pts".to_string());
+            return;
+        }
+        let pts = match Rational::try_from(pts) {
+            Ok(pts) => pts,
+            Err(_) => {
+                self.violation(
+                    at,
+                    "zero-denominator",
+                    format!(
+                        "pts {}/{} has a zero denominator",
+                        pts.get_num(),
+                        pts.get_den()
+                    ),
+                );
+                return;
+            }
+        };
+        if let Some(&last) = self.last_pts.get(&stream) {
+            if pts < last {
+                self.violation(
+                    at,
+                    "non-monotonic-pts",
+                    format!("pts {} comes after {} in stream {}", pts, last, stream),
+                );
+            }
+        }
+        self.last_pts.insert(stream, pts);
+    }
+
+    fn check_audio_frame(&mut self, at: Location, af: audio_frame::Reader) -> capnp::Result<()> {
+        self.check_pts(at, af.has_pts(), af.get_pts()?);
+
+        let len = af.get_data()?.len() as u64;
+        let expected =
+            u64::from(af.get_num_samples()) * u64::from(af.get_channels()) * self.sample_size;
+        if len != expected {
+            self.violation(
+                at,
+                "audio-data-length",
+                format!(
+                    "data is {} bytes, expected {} ({} samples x {} channels x {} bytes)",
+                    len,
+                    expected,
+                    af.get_num_samples(),
+                    af.get_channels(),
+                    self.sample_size
+                ),
+            );
+        }
+        Ok(())
+    }
+
+    fn check_video_frame(&mut self, at: Location, vf: video_frame::Reader) -> capnp::Result<()> {
+        self.check_pts(at, vf.has_pts(), vf.get_pts()?);
+
+        match vf.get_pixel_format() {
+            Ok(video_frame::PixelFormat::Rgba) => {
+                let len = vf.get_data()?.len() as u64;
+                let expected = u64::from(vf.get_width()) * u64::from(vf.get_height()) * 4;
+                if len != expected {
+                    self.violation(
+                        at,
+                        "video-data-length",
+                        format!(
+                            "data is {} bytes, expected {} ({}x{} rgba)",
+                            len,
+                            expected,
+                            vf.get_width(),
+                            vf.get_height()
+                        ),
+                    );
+                }
+            }
+            Err(capnp::NotInSchema(n)) => self.violation(
+                at,
+                "unknown-pixel-format",
+                format!("unknown pixel format {}", n),
+            ),
+        }
+        Ok(())
+    }
+
+    fn check_message(&mut self, message: &FramesMessage) -> capnp::Result<()> {
+        for frame in message.get()?.get_frames()?.iter() {
+            let mut at = Location {
+                message: message.index(),
+                frame: self.report.frames,
+                stream: None,
+            };
+            self.report.frames += 1;
+
+            match frame.which() {
+                Ok(frame::AudioFrame(af)) => {
+                    let af = af?;
+                    at.stream = Some(af.get_stream_id());
+                    self.check_audio_frame(at, af)?;
+                }
+                Ok(frame::VideoFrame(vf)) => {
+                    let vf = vf?;
+                    at.stream = Some(vf.get_stream_id());
+                    self.check_video_frame(at, vf)?;
+                }
+                Err(capnp::NotInSchema(n)) => self.violation(
+                    at,
+                    "unknown-variant",
+                    format!("unknown frame variant {}", n),
+                ),
+            }
+        }
+        Ok(())
+    }
+}
+
+fn print_text(report: &Report) {
+    for v in &report.violations {
+        match v.at.stream {
+            Some(stream) => println!(
+                "frame #{} (message #{}, stream {}): {}: {}",
+                v.at.frame, v.at.message, stream, v.kind, v.detail
+            ),
+            None => println!(
+                "frame #{} (message #{}): {}: {}",
+                v.at.frame, v.at.message, v.kind, v.detail
+            ),
+        }
+    }
+    println!(
+        "{} violations in {} frames",
+        report.violations.len(),
+        report.frames
+    );
+}
+
+pub fn validate_main(args: &[String]) -> Result<(), Error> {
+    let mut input = InputOptions::default();
+    let (mut json, mut sample_size) = (false, 2);
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => json = true,
+            "--sample-size" => sample_size = args::value(arg, args.next())?,
+            _ if input.parse_arg(arg, &mut args)? => {}
+            flag => return Err(args::unknown_option(flag)),
+        }
+    }
+
+    let mut validator = Validator {
+        sample_size,
+        last_pts: HashMap::new(),
+        report: Report {
+            frames: 0,
+            violations: Vec::new(),
+        },
+    };
+    for message in input.open()? {
+        let message = message?;
+        validator
+            .check_message(&message)
+            .map_err(|e| message.explain(e))?;
+    }
+
+    let report = validator.report;
+    if json {
+        let json = serde_json::to_string_pretty(&report).map_err(io::Error::from)?;
+        println!("{}", json);
+    } else {
+        print_text(&report);
+    }
+    match report.violations.len() {
+        0 => Ok(()),
+        n => Err(Error::Invalid(n)),
+    }
+}
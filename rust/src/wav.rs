@@ -0,0 +1,244 @@
+use crate::args::{self, InputOptions};
+use crate::error::Error;
+use crate::frames_capnp::frame;
+use crate::model::AudioFrame;
+use crate::reader::FramesMessage;
+use std::convert::TryFrom;
+use std::fs;
+use std::io::{self, BufWriter, Write};
+
+/// An audio frame of the exported stream, copied out of its message.
+struct AudioChunk {
+    index: usize,
+    frame: AudioFrame,
+}
+
+fn collect_stream(
+    message: &FramesMessage,
+    stream: u32,
+    next_index: &mut usize,
+    chunks: &mut Vec<AudioChunk>,
+) -> Result<(), Error> {
+    let frames = message
+        .get()
+        .and_then(|root| root.get_frames())
+        .map_err(|e| message.explain(e))?;
+    for frame in frames.iter() {
+        let index = *next_index;
+        *next_index += 1;
+
+        let af = match frame.which() {
+            Ok(frame::AudioFrame(af)) => af.map_err(|e| message.explain(e))?,
+            _ => continue,
+        };
+        if af.get_stream_id() != stream {
+            continue;
+        }
+        let frame = AudioFrame::try_from(af).map_err(|e| e.at(message, index))?;
+        chunks.push(AudioChunk { index, frame });
+    }
+    Ok(())
+}
+
+fn check_chunk(chunk: &AudioChunk, channels: u16, sample_size: u16) -> Result<(), Error> {
+    let bad_frame = |reason: String| Error::BadFrame {
+        index: chunk.index,
+        reason,
+    };
+    let af = &chunk.frame;
+    if af.channels != channels {
+        return Err(bad_frame(format!(
+            "has {} channels, but the stream started with {}",
+            af.channels, channels
+        )));
+    }
+    let expected = u64::from(af.num_samples) * u64::from(af.channels) * u64::from(sample_size);
+    if af.data.len() as u64 != expected {
+        return Err(bad_frame(format!(
+            "data is {} bytes, expected {} ({} samples x {} channels x {} bytes)",
+            af.data.len(),
+            expected,
+            af.num_samples,
+            af.channels,
+            sample_size
+        )));
+    }
+    Ok(())
+}
+
+/// Works out the sample rate from the gap between the first two frames:
+/// the first one's samples must exactly fill it. An empty first frame
+/// says nothing about the rate.
+fn infer_sample_rate(chunks: &[AudioChunk]) -> Option<u32> {
+    let (a, b) = (&chunks.first()?.frame, &chunks.get(1)?.frame);
+    let delta = b.pts.checked_sub(a.pts)?;
+    let samples = i128::from(a.num_samples) * i128::from(delta.den());
+    let delta_num = i128::from(delta.num());
+    if delta_num <= 0 || samples % delta_num != 0 {
+        return None;
+    }
+    u32::try_from(samples / delta_num)
+        .ok()
+        .filter(|rate| *rate > 0)
+}
+
+/// Counts the frames whose pts, in samples, is not where the frames
+/// before them end.
+fn count_discontinuities(chunks: &[AudioChunk], sample_rate: u32) -> usize {
+    let mut expected = None;
+    let mut discontinuities = 0;
+    for chunk in chunks {
+        let start = chunk.frame.pts.rescale(sample_rate).ok();
+        if expected.is_some() && start != expected {
+            discontinuities += 1;
+        }
+        expected = start.and_then(|start| start.checked_add(i64::from(chunk.frame.num_samples)));
+    }
+    discontinuities
+}
+
+/// `block_align` is the size of one sample of every channel, in bytes.
+fn write_wav_header<W: Write>(
+    w: &mut W,
+    channels: u16,
+    sample_rate: u32,
+    sample_size: u16,
+    block_align: u16,
+    data_len: u32,
+) -> io::Result<()> {
+    w.write_all(b"RIFF")?;
+    w.write_all(&(36 + data_len).to_le_bytes())?;
+    w.write_all(b"WAVE")?;
+
+    w.write_all(b"fmt ")?;
+    w.write_all(&16u32.to_le_bytes())?;
+    w.write_all(&1u16.to_le_bytes())?; // integer PCM
+    w.write_all(&channels.to_le_bytes())?;
+    w.write_all(&sample_rate.to_le_bytes())?;
+    w.write_all(
+        &sample_rate
+            .saturating_mul(u32::from(block_align))
+            .to_le_bytes(),
+    )?;
+    w.write_all(&block_align.to_le_bytes())?;
+    w.write_all(&(sample_size * 8).to_le_bytes())?;
+
+    w.write_all(b"data")?;
+    w.write_all(&data_len.to_le_bytes())
+}
+
+pub fn export_wav_main(args: &[String]) -> Result<(), Error> {
+    let mut input = InputOptions::default();
+    let (mut stream, mut output, mut sample_rate) = (None, None, None);
+    let mut sample_size: u16 = 2;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--stream" => stream = Some(args::value(arg, args.next())?),
+            "--output" => output = Some(args::value::<String>(arg, args.next())?),
+            "--sample-rate" => sample_rate = Some(args::value(arg, args.next())?),
+            "--sample-size" => sample_size = args::value(arg, args.next())?,
+            _ if input.parse_arg(arg, &mut args)? => {}
+            flag => return Err(args::unknown_option(flag)),
+        }
+    }
+    let stream: u32 = stream.ok_or_else(|| Error::Usage("--stream is required".to_string()))?;
+    if sample_size == 0 || sample_size > 4 {
+        return Err(Error::Usage(
+            "--sample-size must be between 1 and 4 bytes".to_string(),
+        ));
+    }
+    let output = output.unwrap_or_else(|| format!("stream-{}.wav", stream));
+
+    let (mut chunks, mut next_index) = (Vec::new(), 0);
+    for message in input.open()? {
+        collect_stream(&message?, stream, &mut next_index, &mut chunks)?;
+    }
+    let channels = match chunks.first() {
+        Some(chunk) => chunk.frame.channels,
+        None => {
+            return Err(Error::NoFrames(format!(
+                "audio stream {} of {}",
+                stream, input.path
+            )))
+        }
+    };
+    for chunk in &chunks {
+        check_chunk(chunk, channels, sample_size)?;
+    }
+    if channels == 0 {
+        return Err(Error::BadFrame {
+            index: chunks[0].index,
+            reason: "has no channels".to_string(),
+        });
+    }
+    let block_align =
+        u16::try_from(u32::from(channels) * u32::from(sample_size)).map_err(|_| {
+            Error::BadFrame {
+                index: chunks[0].index,
+                reason: format!(
+                    "has {} channels, too many for a WAV file of {}-byte samples",
+                    channels, sample_size
+                ),
+            }
+        })?;
+
+    // Stable, so frames sharing a pts keep their file order.
+    chunks.sort_by_key(|chunk| chunk.frame.pts);
+    let sample_rate = match sample_rate.or_else(|| infer_sample_rate(&chunks)) {
+        Some(0) => return Err(Error::Usage("--sample-rate must be non-zero".to_string())),
+        Some(rate) => rate,
+        None => {
+            return Err(Error::Usage(
+                "cannot infer the sample rate from pts, pass --sample-rate".to_string(),
+            ))
+        }
+    };
+
+    let data_len: usize = chunks.iter().map(|chunk| chunk.frame.data.len()).sum();
+    let data_len = u32::try_from(data_len)
+        .ok()
+        .filter(|len| len.checked_add(36).is_some())
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "stream is too long for a WAV file",
+            )
+        })?;
+
+    let mut w = BufWriter::new(fs::File::create(&output)?);
+    write_wav_header(
+        &mut w,
+        channels,
+        sample_rate,
+        sample_size,
+        block_align,
+        data_len,
+    )?;
+    for chunk in &chunks {
+        w.write_all(&chunk.frame.data)?;
+    }
+    w.flush()?;
+
+    let samples: u64 = chunks
+        .iter()
+        .map(|chunk| u64::from(chunk.frame.num_samples))
+        .sum();
+    let discontinuities = count_discontinuities(&chunks, sample_rate);
+    println!(
+        "Wrote {} frames ({} samples at {} Hz) of stream {} to {}",
+        chunks.len(),
+        samples,
+        sample_rate,
+        stream,
+        output
+    );
+    if discontinuities > 0 {
+        println!(
+            "{} frames do not start where the previous one ends; \
+             the WAV file plays them back to back",
+            discontinuities
+        );
+    }
+    Ok(())
+}
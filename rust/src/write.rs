@@ -0,0 +1,271 @@
+use crate::args;
+use crate::error::Error;
+use crate::frames_capnp::{frames, video_frame};
+use crate::generate::{AudioGenerator, GeneratorError, Pattern, Signal, VideoGenerator};
+use crate::model::Frame;
+use crate::reader::Encoding;
+use std::fs;
+
+pub struct WriteOptions {
+    pub output: String,
+    pub encoding: Encoding,
+    pub audio_frames: u32,
+    pub video_frames: u32,
+    pub audio_stream: u32,
+    pub video_stream: u32,
+    pub channels: u16,
+    /// Samples per audio frame, per channel.
+    pub samples: u32,
+    /// Bytes per sample.
+    pub sample_size: u32,
+    pub sample_rate: u32,
+    pub width: u32,
+    pub height: u32,
+    pub fps: u32,
+    /// What audio frames hold; random bytes if `None`.
+    pub signal: Option<Signal>,
+    /// What video frames hold; random bytes if `None`.
+    pub pattern: Option<Pattern>,
+    pub seed: u64,
+}
+
+impl Default for WriteOptions {
+    fn default() -> Self {
+        WriteOptions {
+            output: "data.bin".to_string(),
+            encoding: Encoding::Unpacked,
+            audio_frames: 1,
+            video_frames: 0,
+            audio_stream: 69,
+            video_stream: 70,
+            channels: 2,
+            samples: 50,
+            sample_size: 2,
+            sample_rate: 3000,
+            width: 4,
+            height: 4,
+            fps: 60,
+            signal: None,
+            pattern: None,
+            seed: 0,
+        }
+    }
+}
+
+impl WriteOptions {
+    pub fn parse(args: &[String]) -> Result<WriteOptions, Error> {
+        let mut opts = WriteOptions::default();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--packed" => opts.encoding = Encoding::Packed,
+                "--audio" => opts.audio_frames = args::value(arg, args.next())?,
+                "--video" => opts.video_frames = args::value(arg, args.next())?,
+                "--audio-stream" => opts.audio_stream = args::value(arg, args.next())?,
+                "--video-stream" => opts.video_stream = args::value(arg, args.next())?,
+                "--channels" => opts.channels = args::value(arg, args.next())?,
+                "--samples" => opts.samples = args::value(arg, args.next())?,
+                "--sample-size" => opts.sample_size = args::value(arg, args.next())?,
+                "--sample-rate" => opts.sample_rate = args::value(arg, args.next())?,
+                "--width" => opts.width = args::value(arg, args.next())?,
+                "--height" => opts.height = args::value(arg, args.next())?,
+                "--fps" => opts.fps = args::value(arg, args.next())?,
+                "--signal" => opts.signal = Some(args::value(arg, args.next())?),
+                "--pattern" => opts.pattern = Some(args::value(arg, args.next())?),
+                "--seed" => opts.seed = args::value(arg, args.next())?,
+                flag if flag.starts_with("--") => return Err(args::unknown_option(flag)),
+                path => opts.output = path.to_string(),
+            }
+        }
+        Ok(opts)
+    }
+}
+
+/// splitmix64, so payloads are reproducible from a seed on every platform.
+struct PayloadRng(u64);
+
+impl PayloadRng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn fill(&mut self, buf: &mut [u8]) {
+        for chunk in buf.chunks_mut(8) {
+            let bytes = self.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+}
+
+enum Kind {
+    Audio,
+    Video,
+}
+
+/// The largest `Data` field capnp can hold, in bytes.
+const MAX_DATA_LEN: u64 = (1 << 29) - 1;
+
+/// The product of `factors`, as the length of a payload described by `what`.
+fn payload_len(what: &str, factors: &[u32]) -> Result<u32, Error> {
+    let len = factors
+        .iter()
+        .try_fold(1u64, |len, &factor| len.checked_mul(u64::from(factor)));
+    match len {
+        Some(len) if len <= MAX_DATA_LEN => Ok(len as u32),
+        _ => Err(Error::Usage(format!(
+            "{} is more than the {} bytes a frame can hold",
+            what, MAX_DATA_LEN
+        ))),
+    }
+}
+
+/// Builds a `Frames` message holding the requested audio and video frames,
+/// interleaved in presentation order.
+pub fn build_frames(
+    opts: &WriteOptions,
+) -> Result<capnp::message::Builder<capnp::message::HeapAllocator>, Error> {
+    if opts.audio_frames > 0 && opts.sample_rate == 0 {
+        return Err(Error::Usage("--sample-rate must be non-zero".to_string()));
+    }
+    if opts.video_frames > 0 && opts.fps == 0 {
+        return Err(Error::Usage("--fps must be non-zero".to_string()));
+    }
+    if opts.signal.is_some() && !(1..=4).contains(&opts.sample_size) {
+        return Err(Error::Usage(
+            "--signal needs a --sample-size between 1 and 4 bytes".to_string(),
+        ));
+    }
+
+    let audio_len = payload_len(
+        "--samples * --channels * --sample-size",
+        &[opts.samples, u32::from(opts.channels), opts.sample_size],
+    )?;
+    let video_len = payload_len("--width * --height * 4", &[opts.width, opts.height, 4])?;
+    if opts
+        .audio_frames
+        .saturating_sub(1)
+        .checked_mul(opts.samples)
+        .is_none()
+    {
+        return Err(Error::Usage(
+            "the pts of the last audio frame, --audio * --samples, does not fit in 32 bits"
+                .to_string(),
+        ));
+    }
+
+    // Audio frame `i` starts at i * samples / sample_rate, video frame `j`
+    // at j / fps: merge the two by cross-multiplying.
+    let mut order = Vec::with_capacity(opts.audio_frames as usize + opts.video_frames as usize);
+    let (mut i, mut j) = (0, 0);
+    while i < opts.audio_frames || j < opts.video_frames {
+        let audio_first = j == opts.video_frames
+            || (i < opts.audio_frames
+                && u64::from(i) * u64::from(opts.samples) * u64::from(opts.fps)
+                    <= u64::from(j) * u64::from(opts.sample_rate));
+        if audio_first {
+            order.push((Kind::Audio, i));
+            i += 1;
+        } else {
+            order.push((Kind::Video, j));
+            j += 1;
+        }
+    }
+
+    let generator_error = |e: GeneratorError| Error::Usage(e.to_string());
+    let audio = match opts.signal {
+        Some(signal) if opts.audio_frames > 0 => Some(
+            AudioGenerator::new(
+                opts.audio_stream,
+                signal,
+                opts.sample_rate,
+                opts.channels,
+                opts.samples,
+                opts.sample_size,
+            )
+            .map_err(generator_error)?,
+        ),
+        _ => None,
+    };
+    let video = match opts.pattern {
+        Some(pattern) if opts.video_frames > 0 => Some(
+            VideoGenerator::new(
+                opts.video_stream,
+                pattern,
+                opts.width,
+                opts.height,
+                opts.fps,
+            )
+            .map_err(generator_error)?,
+        ),
+        _ => None,
+    };
+
+    let mut rng = PayloadRng(opts.seed);
+    let mut message = capnp::message::Builder::new_default();
+    {
+        let root = message.init_root::<frames::Builder>();
+        let mut list = root.init_frames(order.len() as u32);
+        for (index, (kind, n)) in order.into_iter().enumerate() {
+            let frame = list.reborrow().get(index as u32);
+            let generated = match (&kind, &audio, &video) {
+                (Kind::Audio, Some(audio), _) => Some(Frame::Audio(audio.frame(n))),
+                (Kind::Video, _, Some(video)) => Some(Frame::Video(video.frame(n))),
+                _ => None,
+            };
+            if let Some(generated) = generated {
+                generated.write_into(frame).map_err(|e| {
+                    Error::Usage(format!("cannot write the pts of frame {}: {}", index, e))
+                })?;
+                continue;
+            }
+            match kind {
+                Kind::Audio => {
+                    let mut af = frame.init_audio_frame();
+                    af.set_stream_id(opts.audio_stream);
+                    af.set_channels(opts.channels);
+                    af.set_num_samples(opts.samples);
+                    {
+                        let mut pts = af.reborrow().init_pts();
+                        pts.set_num(n * opts.samples);
+                        pts.set_den(opts.sample_rate);
+                    }
+                    rng.fill(af.init_data(audio_len));
+                }
+                Kind::Video => {
+                    let mut vf = frame.init_video_frame();
+                    vf.set_stream_id(opts.video_stream);
+                    vf.set_width(opts.width);
+                    vf.set_height(opts.height);
+                    vf.set_pixel_format(video_frame::PixelFormat::Rgba);
+                    {
+                        let mut pts = vf.reborrow().init_pts();
+                        pts.set_num(n);
+                        pts.set_den(opts.fps);
+                    }
+                    rng.fill(vf.init_data(video_len));
+                }
+            }
+        }
+    }
+    Ok(message)
+}
+
+pub fn write_main(args: &[String]) -> Result<(), Error> {
+    let opts = WriteOptions::parse(args)?;
+    let message = build_frames(&opts)?;
+
+    let mut f = fs::File::create(&opts.output)?;
+    opts.encoding.write_message(&mut f, &message)?;
+    println!(
+        "Wrote {} audio and {} video frames to {} ({})",
+        opts.audio_frames,
+        opts.video_frames,
+        opts.output,
+        opts.encoding.name()
+    );
+    Ok(())
+}